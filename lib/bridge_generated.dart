@@ -7,40 +7,569 @@ import 'dart:async';
 import 'package:meta/meta.dart';
 import 'package:flutter_rust_bridge/flutter_rust_bridge.dart';
 import 'package:uuid/uuid.dart';
+import 'package:freezed_annotation/freezed_annotation.dart' hide protected;
 
 import 'dart:ffi' as ffi;
 
+part 'bridge_generated.freezed.dart';
+
 abstract class SmoldotFlutter {
   Stream<LogEntry> initLogger({dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kInitLoggerConstMeta;
 
-  Future<void> initLightClient({dynamic hint});
+  /// Initializes the default light client, with the default configuration if `config` is `None`.
+  Future<void> initLightClient({LightClientConfig? config, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kInitLightClientConstMeta;
 
-  Future<void> startChainSync(
+  /// Creates a new light client, independent from the default one and from the other instances, with
+  /// the default configuration if `config` is `None`.
+  ///
+  /// The client is shut down once it has been passed to `light_client_shutdown`, or once it and the
+  /// handles to its chains have all been dropped.
+  Future<LightClient> createLightClient(
+      {LightClientConfig? config, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kCreateLightClientConstMeta;
+
+  /// Stops syncing every chain and the JSON-RPC server, and drops the default client, after which
+  /// `init_light_client` can be called again, for example after a hot restart of the Flutter app.
+  ///
+  /// Does nothing if the client isn't initialized.
+  Future<void> shutdownLightClient({dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kShutdownLightClientConstMeta;
+
+  /// Stops syncing every chain and the JSON-RPC server of the given light client, and drops its
+  /// client, after which it can't be used anymore.
+  ///
+  /// Does nothing if the client has already been shut down.
+  Future<void> lightClientShutdown({required LightClient client, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLightClientShutdownConstMeta;
+
+  /// Sets whether the background work of the default light client is limited, for example while the
+  /// app is inactive. While throttled, the timers of the client are postponed so that they fire
+  /// together every few seconds rather than waking the client up one after the other, while the data
+  /// received from peers is still processed as it comes in.
+  ///
+  /// Does nothing if the client isn't initialized.
+  Future<void> throttleLightClient({required bool throttled, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kThrottleLightClientConstMeta;
+
+  /// Sets whether the background work of the given light client is limited, as
+  /// `throttle_light_client` does.
+  Future<void> lightClientThrottle(
+      {required LightClient client, required bool throttled, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLightClientThrottleConstMeta;
+
+  /// Starts syncing a chain on the default light client, returning the handle to pass to the other
+  /// functions.
+  ///
+  /// A parachain must be given the names of the chains started earlier among which to look for its
+  /// relay chain, which is the one whose id matches the `relay_chain` of its specification.
+  ///
+  /// Starting a chain under a name that is already in use shares the existing chain if it was
+  /// started with the same specification, relay chains and options, in which case it keeps syncing
+  /// until every handle to it has been stopped or dropped. Otherwise, an error is returned.
+  ///
+  /// If `database` is empty and the client was configured with a `database_dir`, the chain is
+  /// started from the database last saved for it, if any.
+  ///
+  /// The chain is started with the default options if `options` is `None`.
+  Future<ChainHandle> startChainSync(
       {required String chainName,
       required String chainSpec,
       required String database,
-      String? relayChain,
+      required List<String> relayChains,
+      ChainOptions? options,
       dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kStartChainSyncConstMeta;
 
-  Future<void> stopChainSync({required String chainName, dynamic hint});
+  /// Starts syncing a chain on the given light client, like `start_chain_sync` does on the default
+  /// one.
+  ///
+  /// The relay chains of a parachain are looked for among the chains of the same client only.
+  Future<ChainHandle> lightClientStartChainSync(
+      {required LightClient client,
+      required String chainName,
+      required String chainSpec,
+      required String database,
+      required List<String> relayChains,
+      ChainOptions? options,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLightClientStartChainSyncConstMeta;
+
+  /// Replaces the chain by a new one started with the given parameters, for example to pick up an
+  /// updated chain specification, without affecting the handles to it.
+  ///
+  /// The new chain is started before the old one is removed, so that the old one is left untouched
+  /// if the new one can't be started. The subscriptions and response listeners of the old chain are
+  /// closed, and the requests still waiting for a response from it fail with `ChainRemoved`.
+  ///
+  /// The parachains of a restarted relay chain aren't restarted along with it: they keep syncing
+  /// through the old relay chain, which keeps running in the background until they are restarted or
+  /// stopped themselves.
+  ///
+  /// If `database` is empty, the new chain is started from the database last saved for the chain,
+  /// as with `start_chain_sync`. The new chain is started with the default options if `options` is
+  /// `None`.
+  ///
+  /// The light client runs a single chain per genesis block, so a new chain with the same genesis
+  /// block as the old one keeps syncing from where the old one was rather than from `database`.
+  /// `import_chain_database` restarts a chain from a database instead.
+  Future<void> restartChainSync(
+      {required ChainHandle chain,
+      required String chainSpec,
+      required String database,
+      required List<String> relayChains,
+      ChainOptions? options,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRestartChainSyncConstMeta;
+
+  /// Returns the database of the chain, which can be passed to `start_chain_sync` or
+  /// `import_chain_database` to start syncing the chain from its current finalized block rather than
+  /// from its specification, for example to ship a recent checkpoint along with the app.
+  ///
+  /// The database is trimmed down to `max_size_bytes` if given, by leaving out the information that
+  /// can be retrieved from the network again.
+  Future<String> exportChainDatabase(
+      {required ChainHandle chain, int? maxSizeBytes, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kExportChainDatabaseConstMeta;
+
+  /// Restarts the chain from the given database, keeping its specification, relay chains and
+  /// options, like `restart_chain_sync` does, except that the old chain is removed before the new
+  /// one is started, so that the new one doesn't keep syncing from where the old one was.
+  ///
+  /// Unlike `start_chain_sync`, which silently ignores the databases it can't use, tells whether the
+  /// database was accepted, or why it was ignored. A database that belongs to another chain, or
+  /// without a finalized block that can be decoded, is ignored right away and the chain is left
+  /// untouched. Otherwise, whether the light client accepted the database is told by the finalized
+  /// block of the restarted chain, which is restarted again from the database it had beforehand if
+  /// it didn't. This requires the chain to have a JSON-RPC service, which the chains whose JSON-RPC
+  /// is disabled only have if their database is saved.
+  Future<DatabaseImport> importChainDatabase(
+      {required ChainHandle chain, required String database, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kImportChainDatabaseConstMeta;
+
+  /// Stops syncing a chain, unless other handles to it haven't been stopped or dropped yet.
+  ///
+  /// If `save_timeout_millis` is set and the client was configured with a `database_dir`, the
+  /// finalized database of the chain is saved first, so that the chain resumes syncing from it the
+  /// next time it is started. The chain is stopped regardless once the database has been saved or
+  /// the timeout has elapsed.
+  ///
+  /// The handle can't be used anymore afterwards.
+  Future<void> stopChainSync(
+      {required ChainHandle chain, int? saveTimeoutMillis, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kStopChainSyncConstMeta;
 
+  /// Saves the finalized database of every chain of the default light client, then stops syncing
+  /// them all, for example before the app gets suspended. The chains are stopped regardless once
+  /// their databases have been saved or `save_timeout_millis` has elapsed.
+  ///
+  /// The handles to the chains can't be used anymore afterwards, and the chains have to be started
+  /// again, from their saved databases, to resume syncing. The databases are only saved if the
+  /// client was configured with a `database_dir`.
+  ///
+  /// Does nothing if the client isn't initialized.
+  Future<void> suspendAllChains(
+      {required int saveTimeoutMillis, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSuspendAllChainsConstMeta;
+
+  /// Saves the finalized database of every chain of the given light client, then stops syncing them
+  /// all, as `suspend_all_chains` does.
+  Future<void> lightClientSuspendAllChains(
+      {required LightClient client,
+      required int saveTimeoutMillis,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLightClientSuspendAllChainsConstMeta;
+
+  /// Sends a JSON-RPC request to the chain, whose response is forwarded to the stream returned by
+  /// `listen_json_rpc_responses`.
+  ///
+  /// Ids starting with `smoldot-flutter:` are reserved for the requests issued by the crate itself,
+  /// and a request using one is rejected.
+  ///
+  /// `req` can also be a JSON-RPC batch, whose requests are sent individually and whose responses
+  /// are forwarded as a single batch, in the order of the requests, once they have all been
+  /// received. A request of the batch that can't be sent is answered with an error in the batch.
   Future<void> sendJsonRpcRequest(
-      {required String chainName, required String req, dynamic hint});
+      {required ChainHandle chain, required String req, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kSendJsonRpcRequestConstMeta;
 
+  /// Sends a JSON-RPC request to the chain, and forwards the outcome of the call to `sink` once its
+  /// response has been received, before closing it.
+  ///
+  /// The call is awaited in the background rather than on a thread of the bridge, so that slow
+  /// calls don't hold up the other functions. The id of the request is allocated internally, and
+  /// the response is not forwarded to the stream returned by `listen_json_rpc_responses`. `params`
+  /// must be a JSON array or object.
+  Stream<JsonRpcCallResult> jsonRpcCall(
+      {required ChainHandle chain,
+      required String method,
+      required String params,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kJsonRpcCallConstMeta;
+
+  /// Starts a subscription by calling `method`, and forwards the `result` of each of its
+  /// notifications, as JSON text, to `sink`. If the subscription can't be started, the reason is
+  /// forwarded instead and the stream ends.
+  ///
+  /// The call is awaited in the background, as with `json_rpc_call`. The subscription is cancelled
+  /// by calling `unsubscribe_method` when the Dart stream is cancelled, or when the chain is removed
+  /// through `stop_chain_sync`, including if it only starts once the call has timed out. Its
+  /// notifications are not forwarded to the stream returned by `listen_json_rpc_responses`.
+  Stream<SubscriptionEvent> subscribe(
+      {required ChainHandle chain,
+      required String method,
+      required String params,
+      required String unsubscribeMethod,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSubscribeConstMeta;
+
+  /// Returns a stream of the JSON-RPC responses and notifications of the chain that aren't
+  /// consumed by `json_rpc_call` or `subscribe`.
+  ///
+  /// Any number of streams can listen to the same chain at the same time, and each of them can be
+  /// cancelled independently, or all at once through `unlisten_json_rpc_responses`. A stream
+  /// attached while others are already listening first receives up to `replay` of the most recent
+  /// messages, while a stream attached while none is receives every message produced since the
+  /// previous ones were closed, or since the chain was started.
+  ///
+  /// The messages waiting to be forwarded to each stream are buffered as configured by the
+  /// `listener_buffer_size` and `overflow_policy` options of the chain. So are the messages produced
+  /// while no stream is listening, except that the oldest ones are dropped rather than waiting for a
+  /// stream to make room for them.
+  ///
+  /// Only `listener_buffer_size` messages are forwarded to each stream ahead of their
+  /// acknowledgement through `ack_json_rpc_responses`, so that a Dart listener slower than the chain
+  /// fills up its buffer rather than the memory of the isolate. A stream whose messages are never
+  /// acknowledged therefore stops receiving new ones once that many have been delivered.
   Stream<String> listenJsonRpcResponses(
-      {required String chainName, dynamic hint});
+      {required ChainHandle chain, int? replay, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kListenJsonRpcResponsesConstMeta;
+
+  /// Acknowledges that the Dart side has processed `count` more of the messages delivered to each
+  /// stream returned by `listen_json_rpc_responses` for the chain, letting as many more be forwarded
+  /// to each of them.
+  ///
+  /// The acknowledgement applies to every stream listening to the chain, so messages should only be
+  /// acknowledged once each of those streams has processed them.
+  Future<void> ackJsonRpcResponses(
+      {required ChainHandle chain, required int count, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kAckJsonRpcResponsesConstMeta;
+
+  /// Closes every stream returned by `listen_json_rpc_responses` for the chain, once the messages
+  /// already buffered for them have been delivered.
+  ///
+  /// The messages produced afterwards are kept for the next stream to listen to the chain, as are
+  /// the ones produced once every stream has been cancelled from the Dart side.
+  Future<void> unlistenJsonRpcResponses(
+      {required ChainHandle chain, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kUnlistenJsonRpcResponsesConstMeta;
+
+  /// Returns statistics about the delivery of the JSON-RPC responses of the chain to the streams
+  /// returned by `listen_json_rpc_responses`.
+  Future<JsonRpcMetrics> jsonRpcMetrics(
+      {required ChainHandle chain, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kJsonRpcMetricsConstMeta;
+
+  /// Forwards the sync status of the chain to `sink`, once it is first known, then whenever it
+  /// changes and after every health check. If the status can't be followed, the reason is forwarded
+  /// instead and the stream ends.
+  ///
+  /// The status is followed through the `chain_subscribeNewHeads` and
+  /// `chain_subscribeFinalizedHeads` subscriptions of the chain, which are started in the
+  /// background as with `subscribe`, and through `system_health`, which is called on every new best
+  /// block and every few seconds otherwise. Forwarding the status after every health check, even if
+  /// it is unchanged, lets the subscriptions be cancelled within a few seconds of the Dart stream.
+  /// The stream carries on if the chain is restarted through `restart_chain_sync` or
+  /// `import_chain_database`, and ends once the chain is stopped.
+  Stream<ChainStatusEvent> watchChainStatus(
+      {required ChainHandle chain, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kWatchChainStatusConstMeta;
+
+  /// Starts a JSON-RPC server serving the chains of the default light client over WebSocket, for
+  /// development tools to connect to, and returns the port it listens on.
+  ///
+  /// The server only listens on the loopback interface, on `port` or on a port picked by the OS if
+  /// `None`. A connection to `ws://127.0.0.1:<port>/<chain_name>` is bound to the chain started under
+  /// the given name, and only receives the responses to its own requests and the notifications of
+  /// its own subscriptions. Any server previously started on the client is stopped first.
+  ///
+  /// A connection follows the chain through `restart_chain_sync` and `import_chain_database`,
+  /// except that its subscriptions end, each with a `smoldotFlutter_subscriptionClosed`
+  /// notification whose `params` hold the id of the `subscription`. Once the chain is stopped, its
+  /// connections are closed.
+  ///
+  /// Web pages opened in a browser on the device could connect to the server as well, and are told
+  /// apart by the `Origin` header browsers send: their connections are rejected, unless their origin
+  /// (such as `https://polkadot.js.org`) is among `allowed_origins`.
+  ///
+  /// Only available if the crate is built with its `server` feature.
+  Future<int> startJsonRpcServer(
+      {int? port, required List<String> allowedOrigins, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStartJsonRpcServerConstMeta;
+
+  /// Starts a JSON-RPC server serving the chains of the given light client, like
+  /// `start_json_rpc_server` does for the default one.
+  ///
+  /// The client isn't shut down when dropped for as long as its server is running.
+  Future<int> lightClientStartJsonRpcServer(
+      {required LightClient client,
+      int? port,
+      required List<String> allowedOrigins,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLightClientStartJsonRpcServerConstMeta;
+
+  /// Stops the JSON-RPC server of the default light client, closing its connections.
+  ///
+  /// Does nothing if the server isn't running.
+  Future<void> stopJsonRpcServer({dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopJsonRpcServerConstMeta;
+
+  /// Stops the JSON-RPC server of the given light client, like `stop_json_rpc_server` does for the
+  /// default one.
+  Future<void> lightClientStopJsonRpcServer(
+      {required LightClient client, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLightClientStopJsonRpcServerConstMeta;
+
+  DropFnType get dropOpaqueChainHandle;
+  ShareFnType get shareOpaqueChainHandle;
+  OpaqueTypeFinalizer get ChainHandleFinalizer;
+
+  DropFnType get dropOpaqueLightClient;
+  ShareFnType get shareOpaqueLightClient;
+  OpaqueTypeFinalizer get LightClientFinalizer;
+}
+
+@sealed
+class ChainHandle extends FrbOpaque {
+  final SmoldotFlutter bridge;
+  ChainHandle.fromRaw(int ptr, int size, this.bridge)
+      : super.unsafe(ptr, size);
+  @override
+  DropFnType get dropFn => bridge.dropOpaqueChainHandle;
+
+  @override
+  ShareFnType get shareFn => bridge.shareOpaqueChainHandle;
+
+  @override
+  OpaqueTypeFinalizer get staticFinalizer => bridge.ChainHandleFinalizer;
+}
+
+@sealed
+class LightClient extends FrbOpaque {
+  final SmoldotFlutter bridge;
+  LightClient.fromRaw(int ptr, int size, this.bridge)
+      : super.unsafe(ptr, size);
+  @override
+  DropFnType get dropFn => bridge.dropOpaqueLightClient;
+
+  @override
+  ShareFnType get shareFn => bridge.shareOpaqueLightClient;
+
+  @override
+  OpaqueTypeFinalizer get staticFinalizer => bridge.LightClientFinalizer;
+}
+
+/// Options of a chain. Fields left unset take their default value.
+class ChainOptions {
+  /// Maximum number of JSON-RPC requests waiting to be processed by the chain, beyond which new
+  /// ones are rejected. Defaults to 128, and is raised to 1 if 0.
+  final int? maxPendingRequests;
+
+  /// Maximum number of active JSON-RPC subscriptions, beyond which new ones are rejected.
+  /// Defaults to 1024.
+  final int? maxSubscriptions;
+
+  /// Whether the chain doesn't handle JSON-RPC requests at all, for example because it only
+  /// serves as the relay chain of parachains, in which case the functions sending requests to it
+  /// or listening to its responses return a `JsonRpcDisabled` error. Defaults to `false`.
+  ///
+  /// If the client was configured with a `database_dir`, the database of the chain is still
+  /// saved, which takes a JSON-RPC service: the chain then keeps one for the crate's own use,
+  /// limited to a few pending requests and no subscriptions, but with the memory and background
+  /// work any JSON-RPC service of smoldot involves.
+  final bool? disableJsonRpc;
+
+  /// Maximum number of messages waiting to be forwarded to each stream returned by
+  /// `listen_json_rpc_responses`, or kept while no stream is listening. Also the number of
+  /// messages forwarded to each stream ahead of their acknowledgement through
+  /// `ack_json_rpc_responses`. Defaults to 1024, and is raised to 1 if 0.
+  final int? listenerBufferSize;
+
+  /// What happens to the messages of a listener whose buffer is full. Defaults to
+  /// `DropNotifications`.
+  final OverflowPolicy? overflowPolicy;
+
+  const ChainOptions({
+    this.maxPendingRequests,
+    this.maxSubscriptions,
+    this.disableJsonRpc,
+    this.listenerBufferSize,
+    this.overflowPolicy,
+  });
+}
+
+/// Sync status of a chain, as forwarded by `watch_chain_status`.
+class ChainStatus {
+  /// Number of the best block, or `None` until it is known.
+  final int? bestBlockNumber;
+
+  /// Hash of the best block, in `0x`-prefixed hexadecimal, or `None` until it is known.
+  final String? bestBlockHash;
+
+  /// Number of the latest finalized block, or `None` until it is known.
+  final int? finalizedBlockNumber;
+
+  /// Hash of the latest finalized block, in `0x`-prefixed hexadecimal, or `None` until it is
+  /// known.
+  final String? finalizedBlockHash;
+
+  /// Number of peers the chain is syncing from.
+  final int peers;
+
+  /// Whether the chain is still catching up with its head, in which case its best and finalized
+  /// blocks aren't recent.
+  final bool isSyncing;
+
+  /// Progress of the warp sync of the chain.
+  final WarpSyncPhase warpSyncPhase;
+
+  const ChainStatus({
+    this.bestBlockNumber,
+    this.bestBlockHash,
+    this.finalizedBlockNumber,
+    this.finalizedBlockHash,
+    required this.peers,
+    required this.isSyncing,
+    required this.warpSyncPhase,
+  });
+}
+
+/// Event of the stream returned by `watch_chain_status`.
+@freezed
+class ChainStatusEvent with _$ChainStatusEvent {
+  /// The current sync status of the chain.
+  const factory ChainStatusEvent.status(
+    ChainStatus field0,
+  ) = ChainStatusEvent_Status;
+
+  /// Why the status of the chain couldn't be followed, after which the stream ends.
+  const factory ChainStatusEvent.err(
+    SmoldotFlutterError field0,
+  ) = ChainStatusEvent_Err;
+}
+
+/// What `import_chain_database` made of a database.
+enum DatabaseImport {
+  /// The chain was restarted from the database.
+  accepted,
+  /// The database isn't in a format the light client understands.
+  malformed,
+  /// The database belongs to another chain.
+  wrongChain,
+  /// The database doesn't contain a finalized block more recent than the checkpoint of the chain
+  /// specification, which the chain keeps syncing from.
+  outdated,
+}
+
+/// Outcome of a call made through `json_rpc_call`.
+@freezed
+class JsonRpcCallResult with _$JsonRpcCallResult {
+  /// The `result` of the response, as JSON text.
+  const factory JsonRpcCallResult.ok(
+    String field0,
+  ) = JsonRpcCallResult_Ok;
+
+  /// Why no `result` could be obtained.
+  const factory JsonRpcCallResult.err(
+    SmoldotFlutterError field0,
+  ) = JsonRpcCallResult_Err;
+}
+
+/// Statistics about the delivery of the JSON-RPC responses of a chain to the streams returned by
+/// `listen_json_rpc_responses`.
+class JsonRpcMetrics {
+  /// Number of messages dropped because a stream didn't keep up with the chain, counted once per
+  /// stream, or because no stream was listening for too long.
+  final int droppedMessages;
+
+  /// Number of messages currently waiting to be forwarded, summed over every stream, or kept
+  /// while no stream is listening.
+  final int bufferedMessages;
+
+  const JsonRpcMetrics({
+    required this.droppedMessages,
+    required this.bufferedMessages,
+  });
+}
+
+/// Configuration of a light client. Fields left unset take their default value.
+class LightClientConfig {
+  /// Name under which the client identifies itself to its peers. Defaults to the name of this
+  /// crate.
+  final String? clientName;
+
+  /// Version under which the client identifies itself to its peers. Defaults to the version of
+  /// this crate.
+  final String? clientVersion;
+
+  /// Whether peers can be connected to over plain TCP. Defaults to `true`.
+  final bool? allowTcp;
+
+  /// Whether peers can be connected to over non-secure WebSocket. Defaults to `true`.
+  final bool? allowWebsocket;
+
+  /// Directory in which the finalized database of each chain is saved periodically, and from
+  /// which it is loaded when the chain is started without a database. The databases aren't
+  /// persisted if unset.
+  ///
+  /// The chains whose JSON-RPC is disabled through `ChainOptions` are saved as well: their
+  /// database is obtained through a JSON-RPC service that only the client itself uses.
+  ///
+  /// A saved database that is corrupted, belongs to another chain or can't be decoded by the
+  /// version of smoldot the client is built with is ignored, and the chain starts from the
+  /// checkpoint of its specification instead.
+  final String? databaseDir;
+
+  /// Number of seconds between two saves of the database of each chain. Defaults to 60, and is
+  /// raised to 1 if 0.
+  final int? databaseSnapshotIntervalSecs;
+
+  const LightClientConfig({
+    this.clientName,
+    this.clientVersion,
+    this.allowTcp,
+    this.allowWebsocket,
+    this.databaseDir,
+    this.databaseSnapshotIntervalSecs,
+  });
 }
 
 class LogEntry {
@@ -57,6 +586,94 @@ class LogEntry {
   });
 }
 
+/// What happens to a message broadcast to a listener whose buffer is full, which happens when
+/// messages are produced faster than the Dart side acknowledges them.
+enum OverflowPolicy {
+  /// Waits for the listener to catch up, which pauses the processing of every response of the
+  /// chain in the meantime, including the ones awaited by `json_rpc_call`.
+  block,
+  /// Drops the oldest message buffered for the listener.
+  dropOldest,
+  /// Drops the oldest notification buffered for the listener, or the new message if it is a
+  /// notification itself. Waits for the listener to catch up, as with `Block`, if neither is a
+  /// notification.
+  dropNotifications,
+}
+
+/// Error returned by the functions exposed to Dart.
+class SmoldotFlutterError implements FrbException {
+  /// What went wrong, for the caller to react to.
+  final SmoldotFlutterErrorKind kind;
+
+  /// Human-readable description of the error.
+  final String message;
+
+  const SmoldotFlutterError({
+    required this.kind,
+    required this.message,
+  });
+}
+
+enum SmoldotFlutterErrorKind {
+  /// `init_light_client` hasn't been called.
+  notInitialized,
+  /// `init_light_client` has already been called.
+  alreadyInitialized,
+  /// No chain with the given name has been started.
+  unknownChain,
+  /// A chain with the given name has already been started.
+  duplicateChain,
+  /// The chain specification or database couldn't be used to start the chain.
+  invalidChainSpec,
+  /// The relay chain of a parachain couldn't be determined.
+  relayChainNotFound,
+  /// Too many JSON-RPC requests are waiting to be processed by the chain.
+  queueFull,
+  /// The JSON-RPC request or its parameters are malformed.
+  invalidRequest,
+  /// The chain was started with its JSON-RPC service disabled.
+  jsonRpcDisabled,
+  /// The chain answered the JSON-RPC request with an error.
+  jsonRpcError,
+  /// The JSON-RPC response couldn't be decoded.
+  malformedResponse,
+  /// No JSON-RPC response was received within the allotted time.
+  timeout,
+  /// The chain was removed before the JSON-RPC response was received.
+  chainRemoved,
+  /// A previous call panicked while holding the state of the client.
+  poisoned,
+  /// The JSON-RPC server couldn't be started, because the crate was built without its `server`
+  /// feature or because the port is already in use.
+  serverUnavailable,
+}
+
+/// Event of a subscription started through `subscribe`.
+@freezed
+class SubscriptionEvent with _$SubscriptionEvent {
+  /// The `result` of a notification, as JSON text.
+  const factory SubscriptionEvent.notification(
+    String field0,
+  ) = SubscriptionEvent_Notification;
+
+  /// Why the subscription couldn't be started, after which the stream ends.
+  const factory SubscriptionEvent.err(
+    SmoldotFlutterError field0,
+  ) = SubscriptionEvent_Err;
+}
+
+/// Progress of the warp sync of a chain, which catches up with the finalized block of its peers
+/// before following the chain block by block, as far as its JSON-RPC service tells.
+enum WarpSyncPhase {
+  /// The chain has no peer to warp sync from yet.
+  waitingForPeers,
+  /// The chain is warp syncing from its peers.
+  inProgress,
+  /// The chain has caught up with its head once. It is considered syncing again if it falls
+  /// behind afterwards, but doesn't warp sync again.
+  finished,
+}
+
 class SmoldotFlutterImpl implements SmoldotFlutter {
   final SmoldotFlutterPlatform _platform;
   factory SmoldotFlutterImpl(ExternalLibrary dylib) =>
@@ -82,12 +699,14 @@ class SmoldotFlutterImpl implements SmoldotFlutter {
         argNames: [],
       );
 
-  Future<void> initLightClient({dynamic hint}) {
+  Future<void> initLightClient({LightClientConfig? config, dynamic hint}) {
+    var arg0 = _platform.api2wire_opt_box_autoadd_light_client_config(config);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_init_light_client(port_),
+      callFfi: (port_) => _platform.inner.wire_init_light_client(port_, arg0),
       parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
       constMeta: kInitLightClientConstMeta,
-      argValues: [],
+      argValues: [config],
       hint: hint,
     ));
   }
@@ -95,25 +714,125 @@ class SmoldotFlutterImpl implements SmoldotFlutter {
   FlutterRustBridgeTaskConstMeta get kInitLightClientConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
         debugName: "init_light_client",
+        argNames: ["config"],
+      );
+
+  Future<LightClient> createLightClient(
+      {LightClientConfig? config, dynamic hint}) {
+    var arg0 = _platform.api2wire_opt_box_autoadd_light_client_config(config);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_create_light_client(port_, arg0),
+      parseSuccessData: _wire2api_LightClient,
+      parseErrorData: null,
+      constMeta: kCreateLightClientConstMeta,
+      argValues: [config],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kCreateLightClientConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "create_light_client",
+        argNames: ["config"],
+      );
+
+  Future<void> shutdownLightClient({dynamic hint}) {
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_shutdown_light_client(port_),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kShutdownLightClientConstMeta,
+      argValues: [],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kShutdownLightClientConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "shutdown_light_client",
         argNames: [],
       );
 
-  Future<void> startChainSync(
+  Future<void> lightClientShutdown(
+      {required LightClient client, dynamic hint}) {
+    var arg0 = _platform.api2wire_LightClient(client);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_light_client_shutdown(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kLightClientShutdownConstMeta,
+      argValues: [client],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLightClientShutdownConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "light_client_shutdown",
+        argNames: ["client"],
+      );
+
+  Future<void> throttleLightClient({required bool throttled, dynamic hint}) {
+    var arg0 = throttled;
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_throttle_light_client(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kThrottleLightClientConstMeta,
+      argValues: [throttled],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kThrottleLightClientConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "throttle_light_client",
+        argNames: ["throttled"],
+      );
+
+  Future<void> lightClientThrottle(
+      {required LightClient client, required bool throttled, dynamic hint}) {
+    var arg0 = _platform.api2wire_LightClient(client);
+    var arg1 = throttled;
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_light_client_throttle(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kLightClientThrottleConstMeta,
+      argValues: [client, throttled],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLightClientThrottleConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "light_client_throttle",
+        argNames: ["client", "throttled"],
+      );
+
+  Future<ChainHandle> startChainSync(
       {required String chainName,
       required String chainSpec,
       required String database,
-      String? relayChain,
+      required List<String> relayChains,
+      ChainOptions? options,
       dynamic hint}) {
     var arg0 = _platform.api2wire_String(chainName);
     var arg1 = _platform.api2wire_String(chainSpec);
     var arg2 = _platform.api2wire_String(database);
-    var arg3 = _platform.api2wire_opt_String(relayChain);
+    var arg3 = _platform.api2wire_StringList(relayChains);
+    var arg4 = _platform.api2wire_opt_box_autoadd_chain_options(options);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) =>
-          _platform.inner.wire_start_chain_sync(port_, arg0, arg1, arg2, arg3),
-      parseSuccessData: _wire2api_unit,
+      callFfi: (port_) => _platform.inner
+          .wire_start_chain_sync(port_, arg0, arg1, arg2, arg3, arg4),
+      parseSuccessData: _wire2api_ChainHandle,
+      parseErrorData: _wire2api_smoldot_flutter_error,
       constMeta: kStartChainSyncConstMeta,
-      argValues: [chainName, chainSpec, database, relayChain],
+      argValues: [chainName, chainSpec, database, relayChains, options],
       hint: hint,
     ));
   }
@@ -121,16 +840,135 @@ class SmoldotFlutterImpl implements SmoldotFlutter {
   FlutterRustBridgeTaskConstMeta get kStartChainSyncConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
         debugName: "start_chain_sync",
-        argNames: ["chainName", "chainSpec", "database", "relayChain"],
+        argNames: [
+          "chainName",
+          "chainSpec",
+          "database",
+          "relayChains",
+          "options"
+        ],
       );
 
-  Future<void> stopChainSync({required String chainName, dynamic hint}) {
-    var arg0 = _platform.api2wire_String(chainName);
+  Future<ChainHandle> lightClientStartChainSync(
+      {required LightClient client,
+      required String chainName,
+      required String chainSpec,
+      required String database,
+      required List<String> relayChains,
+      ChainOptions? options,
+      dynamic hint}) {
+    var arg0 = _platform.api2wire_LightClient(client);
+    var arg1 = _platform.api2wire_String(chainName);
+    var arg2 = _platform.api2wire_String(chainSpec);
+    var arg3 = _platform.api2wire_String(database);
+    var arg4 = _platform.api2wire_StringList(relayChains);
+    var arg5 = _platform.api2wire_opt_box_autoadd_chain_options(options);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_stop_chain_sync(port_, arg0),
+      callFfi: (port_) => _platform.inner.wire_light_client_start_chain_sync(
+          port_, arg0, arg1, arg2, arg3, arg4, arg5),
+      parseSuccessData: _wire2api_ChainHandle,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kLightClientStartChainSyncConstMeta,
+      argValues: [client, chainName, chainSpec, database, relayChains, options],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLightClientStartChainSyncConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "light_client_start_chain_sync",
+        argNames: [
+          "client",
+          "chainName",
+          "chainSpec",
+          "database",
+          "relayChains",
+          "options"
+        ],
+      );
+
+  Future<void> restartChainSync(
+      {required ChainHandle chain,
+      required String chainSpec,
+      required String database,
+      required List<String> relayChains,
+      ChainOptions? options,
+      dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = _platform.api2wire_String(chainSpec);
+    var arg2 = _platform.api2wire_String(database);
+    var arg3 = _platform.api2wire_StringList(relayChains);
+    var arg4 = _platform.api2wire_opt_box_autoadd_chain_options(options);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner
+          .wire_restart_chain_sync(port_, arg0, arg1, arg2, arg3, arg4),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kRestartChainSyncConstMeta,
+      argValues: [chain, chainSpec, database, relayChains, options],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRestartChainSyncConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "restart_chain_sync",
+        argNames: ["chain", "chainSpec", "database", "relayChains", "options"],
+      );
+
+  Future<String> exportChainDatabase(
+      {required ChainHandle chain, int? maxSizeBytes, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = _platform.api2wire_opt_box_autoadd_u64(maxSizeBytes);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_export_chain_database(port_, arg0, arg1),
+      parseSuccessData: _wire2api_String,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kExportChainDatabaseConstMeta,
+      argValues: [chain, maxSizeBytes],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kExportChainDatabaseConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "export_chain_database",
+        argNames: ["chain", "maxSizeBytes"],
+      );
+
+  Future<DatabaseImport> importChainDatabase(
+      {required ChainHandle chain, required String database, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = _platform.api2wire_String(database);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_import_chain_database(port_, arg0, arg1),
+      parseSuccessData: _wire2api_database_import,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kImportChainDatabaseConstMeta,
+      argValues: [chain, database],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kImportChainDatabaseConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "import_chain_database",
+        argNames: ["chain", "database"],
+      );
+
+  Future<void> stopChainSync(
+      {required ChainHandle chain, int? saveTimeoutMillis, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = _platform.api2wire_opt_box_autoadd_u32(saveTimeoutMillis);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_stop_chain_sync(port_, arg0, arg1),
       parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
       constMeta: kStopChainSyncConstMeta,
-      argValues: [chainName],
+      argValues: [chain, saveTimeoutMillis],
       hint: hint,
     ));
   }
@@ -138,19 +976,62 @@ class SmoldotFlutterImpl implements SmoldotFlutter {
   FlutterRustBridgeTaskConstMeta get kStopChainSyncConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
         debugName: "stop_chain_sync",
-        argNames: ["chainName"],
+        argNames: ["chain", "saveTimeoutMillis"],
+      );
+
+  Future<void> suspendAllChains(
+      {required int saveTimeoutMillis, dynamic hint}) {
+    var arg0 = api2wire_u32(saveTimeoutMillis);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_suspend_all_chains(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kSuspendAllChainsConstMeta,
+      argValues: [saveTimeoutMillis],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSuspendAllChainsConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "suspend_all_chains",
+        argNames: ["saveTimeoutMillis"],
+      );
+
+  Future<void> lightClientSuspendAllChains(
+      {required LightClient client,
+      required int saveTimeoutMillis,
+      dynamic hint}) {
+    var arg0 = _platform.api2wire_LightClient(client);
+    var arg1 = api2wire_u32(saveTimeoutMillis);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner
+          .wire_light_client_suspend_all_chains(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kLightClientSuspendAllChainsConstMeta,
+      argValues: [client, saveTimeoutMillis],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLightClientSuspendAllChainsConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "light_client_suspend_all_chains",
+        argNames: ["client", "saveTimeoutMillis"],
       );
 
   Future<void> sendJsonRpcRequest(
-      {required String chainName, required String req, dynamic hint}) {
-    var arg0 = _platform.api2wire_String(chainName);
+      {required ChainHandle chain, required String req, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
     var arg1 = _platform.api2wire_String(req);
     return _platform.executeNormal(FlutterRustBridgeTask(
       callFfi: (port_) =>
           _platform.inner.wire_send_json_rpc_request(port_, arg0, arg1),
       parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
       constMeta: kSendJsonRpcRequestConstMeta,
-      argValues: [chainName, req],
+      argValues: [chain, req],
       hint: hint,
     ));
   }
@@ -158,18 +1039,72 @@ class SmoldotFlutterImpl implements SmoldotFlutter {
   FlutterRustBridgeTaskConstMeta get kSendJsonRpcRequestConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
         debugName: "send_json_rpc_request",
-        argNames: ["chainName", "req"],
+        argNames: ["chain", "req"],
+      );
+
+  Stream<JsonRpcCallResult> jsonRpcCall(
+      {required ChainHandle chain,
+      required String method,
+      required String params,
+      dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = _platform.api2wire_String(method);
+    var arg2 = _platform.api2wire_String(params);
+    return _platform.executeStream(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_json_rpc_call(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_json_rpc_call_result,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kJsonRpcCallConstMeta,
+      argValues: [chain, method, params],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kJsonRpcCallConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "json_rpc_call",
+        argNames: ["chain", "method", "params"],
+      );
+
+  Stream<SubscriptionEvent> subscribe(
+      {required ChainHandle chain,
+      required String method,
+      required String params,
+      required String unsubscribeMethod,
+      dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = _platform.api2wire_String(method);
+    var arg2 = _platform.api2wire_String(params);
+    var arg3 = _platform.api2wire_String(unsubscribeMethod);
+    return _platform.executeStream(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_subscribe(port_, arg0, arg1, arg2, arg3),
+      parseSuccessData: _wire2api_subscription_event,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kSubscribeConstMeta,
+      argValues: [chain, method, params, unsubscribeMethod],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSubscribeConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "subscribe",
+        argNames: ["chain", "method", "params", "unsubscribeMethod"],
       );
 
   Stream<String> listenJsonRpcResponses(
-      {required String chainName, dynamic hint}) {
-    var arg0 = _platform.api2wire_String(chainName);
+      {required ChainHandle chain, int? replay, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = _platform.api2wire_opt_box_autoadd_u32(replay);
     return _platform.executeStream(FlutterRustBridgeTask(
       callFfi: (port_) =>
-          _platform.inner.wire_listen_json_rpc_responses(port_, arg0),
+          _platform.inner.wire_listen_json_rpc_responses(port_, arg0, arg1),
       parseSuccessData: _wire2api_String,
+      parseErrorData: _wire2api_smoldot_flutter_error,
       constMeta: kListenJsonRpcResponsesConstMeta,
-      argValues: [chainName],
+      argValues: [chain, replay],
       hint: hint,
     ));
   }
@@ -177,53 +1112,395 @@ class SmoldotFlutterImpl implements SmoldotFlutter {
   FlutterRustBridgeTaskConstMeta get kListenJsonRpcResponsesConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
         debugName: "listen_json_rpc_responses",
-        argNames: ["chainName"],
+        argNames: ["chain", "replay"],
       );
 
-  void dispose() {
-    _platform.dispose();
+  Future<void> ackJsonRpcResponses(
+      {required ChainHandle chain, required int count, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    var arg1 = api2wire_u32(count);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_ack_json_rpc_responses(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kAckJsonRpcResponsesConstMeta,
+      argValues: [chain, count],
+      hint: hint,
+    ));
   }
-// Section: wire2api
 
-  String _wire2api_String(dynamic raw) {
-    return raw as String;
-  }
+  FlutterRustBridgeTaskConstMeta get kAckJsonRpcResponsesConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "ack_json_rpc_responses",
+        argNames: ["chain", "count"],
+      );
 
-  int _wire2api_i32(dynamic raw) {
-    return raw as int;
+  Future<void> unlistenJsonRpcResponses(
+      {required ChainHandle chain, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_unlisten_json_rpc_responses(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kUnlistenJsonRpcResponsesConstMeta,
+      argValues: [chain],
+      hint: hint,
+    ));
   }
 
-  int _wire2api_i64(dynamic raw) {
-    return castInt(raw);
-  }
+  FlutterRustBridgeTaskConstMeta get kUnlistenJsonRpcResponsesConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "unlisten_json_rpc_responses",
+        argNames: ["chain"],
+      );
 
-  LogEntry _wire2api_log_entry(dynamic raw) {
-    final arr = raw as List<dynamic>;
-    if (arr.length != 4)
-      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
-    return LogEntry(
-      timeMillis: _wire2api_i64(arr[0]),
-      level: _wire2api_i32(arr[1]),
-      tag: _wire2api_String(arr[2]),
-      msg: _wire2api_String(arr[3]),
-    );
+  Future<JsonRpcMetrics> jsonRpcMetrics(
+      {required ChainHandle chain, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_json_rpc_metrics(port_, arg0),
+      parseSuccessData: _wire2api_json_rpc_metrics,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kJsonRpcMetricsConstMeta,
+      argValues: [chain],
+      hint: hint,
+    ));
   }
 
-  int _wire2api_u8(dynamic raw) {
-    return raw as int;
-  }
+  FlutterRustBridgeTaskConstMeta get kJsonRpcMetricsConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "json_rpc_metrics",
+        argNames: ["chain"],
+      );
 
-  Uint8List _wire2api_uint_8_list(dynamic raw) {
-    return raw as Uint8List;
+  Stream<ChainStatusEvent> watchChainStatus(
+      {required ChainHandle chain, dynamic hint}) {
+    var arg0 = _platform.api2wire_ChainHandle(chain);
+    return _platform.executeStream(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_watch_chain_status(port_, arg0),
+      parseSuccessData: _wire2api_chain_status_event,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kWatchChainStatusConstMeta,
+      argValues: [chain],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kWatchChainStatusConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "watch_chain_status",
+        argNames: ["chain"],
+      );
+
+  Future<int> startJsonRpcServer(
+      {int? port, required List<String> allowedOrigins, dynamic hint}) {
+    var arg0 = _platform.api2wire_opt_box_autoadd_u16(port);
+    var arg1 = _platform.api2wire_StringList(allowedOrigins);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_start_json_rpc_server(port_, arg0, arg1),
+      parseSuccessData: _wire2api_u16,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kStartJsonRpcServerConstMeta,
+      argValues: [port, allowedOrigins],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStartJsonRpcServerConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "start_json_rpc_server",
+        argNames: ["port", "allowedOrigins"],
+      );
+
+  Future<int> lightClientStartJsonRpcServer(
+      {required LightClient client,
+      int? port,
+      required List<String> allowedOrigins,
+      dynamic hint}) {
+    var arg0 = _platform.api2wire_LightClient(client);
+    var arg1 = _platform.api2wire_opt_box_autoadd_u16(port);
+    var arg2 = _platform.api2wire_StringList(allowedOrigins);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner
+          .wire_light_client_start_json_rpc_server(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_u16,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kLightClientStartJsonRpcServerConstMeta,
+      argValues: [client, port, allowedOrigins],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLightClientStartJsonRpcServerConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "light_client_start_json_rpc_server",
+        argNames: ["client", "port", "allowedOrigins"],
+      );
+
+  Future<void> stopJsonRpcServer({dynamic hint}) {
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_stop_json_rpc_server(port_),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kStopJsonRpcServerConstMeta,
+      argValues: [],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopJsonRpcServerConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_json_rpc_server",
+        argNames: [],
+      );
+
+  Future<void> lightClientStopJsonRpcServer(
+      {required LightClient client, dynamic hint}) {
+    var arg0 = _platform.api2wire_LightClient(client);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_light_client_stop_json_rpc_server(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_smoldot_flutter_error,
+      constMeta: kLightClientStopJsonRpcServerConstMeta,
+      argValues: [client],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLightClientStopJsonRpcServerConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "light_client_stop_json_rpc_server",
+        argNames: ["client"],
+      );
+
+  DropFnType get dropOpaqueChainHandle =>
+      _platform.inner.drop_opaque_ChainHandle;
+  ShareFnType get shareOpaqueChainHandle =>
+      _platform.inner.share_opaque_ChainHandle;
+  OpaqueTypeFinalizer get ChainHandleFinalizer =>
+      _platform.ChainHandleFinalizer;
+
+  DropFnType get dropOpaqueLightClient =>
+      _platform.inner.drop_opaque_LightClient;
+  ShareFnType get shareOpaqueLightClient =>
+      _platform.inner.share_opaque_LightClient;
+  OpaqueTypeFinalizer get LightClientFinalizer =>
+      _platform.LightClientFinalizer;
+
+  void dispose() {
+    _platform.dispose();
+  }
+// Section: wire2api
+
+  ChainHandle _wire2api_ChainHandle(dynamic raw) {
+    return ChainHandle.fromRaw(raw[0], raw[1], this);
+  }
+
+  LightClient _wire2api_LightClient(dynamic raw) {
+    return LightClient.fromRaw(raw[0], raw[1], this);
+  }
+
+  String _wire2api_String(dynamic raw) {
+    return raw as String;
+  }
+
+  bool _wire2api_bool(dynamic raw) {
+    return raw as bool;
+  }
+
+  ChainStatus _wire2api_box_autoadd_chain_status(dynamic raw) {
+    return _wire2api_chain_status(raw);
+  }
+
+  SmoldotFlutterError _wire2api_box_autoadd_smoldot_flutter_error(dynamic raw) {
+    return _wire2api_smoldot_flutter_error(raw);
+  }
+
+  int _wire2api_box_autoadd_u64(dynamic raw) {
+    return _wire2api_u64(raw);
+  }
+
+  ChainStatus _wire2api_chain_status(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 7)
+      throw Exception('unexpected arr length: expect 7 but see ${arr.length}');
+    return ChainStatus(
+      bestBlockNumber: _wire2api_opt_box_autoadd_u64(arr[0]),
+      bestBlockHash: _wire2api_opt_String(arr[1]),
+      finalizedBlockNumber: _wire2api_opt_box_autoadd_u64(arr[2]),
+      finalizedBlockHash: _wire2api_opt_String(arr[3]),
+      peers: _wire2api_u32(arr[4]),
+      isSyncing: _wire2api_bool(arr[5]),
+      warpSyncPhase: _wire2api_warp_sync_phase(arr[6]),
+    );
+  }
+
+  ChainStatusEvent _wire2api_chain_status_event(dynamic raw) {
+    switch (raw[0]) {
+      case 0:
+        return ChainStatusEvent_Status(
+          _wire2api_box_autoadd_chain_status(raw[1]),
+        );
+      case 1:
+        return ChainStatusEvent_Err(
+          _wire2api_box_autoadd_smoldot_flutter_error(raw[1]),
+        );
+      default:
+        throw Exception("unreachable");
+    }
+  }
+
+  DatabaseImport _wire2api_database_import(dynamic raw) {
+    return DatabaseImport.values[raw as int];
+  }
+
+  int _wire2api_i32(dynamic raw) {
+    return raw as int;
+  }
+
+  int _wire2api_i64(dynamic raw) {
+    return castInt(raw);
+  }
+
+  JsonRpcCallResult _wire2api_json_rpc_call_result(dynamic raw) {
+    switch (raw[0]) {
+      case 0:
+        return JsonRpcCallResult_Ok(
+          _wire2api_String(raw[1]),
+        );
+      case 1:
+        return JsonRpcCallResult_Err(
+          _wire2api_box_autoadd_smoldot_flutter_error(raw[1]),
+        );
+      default:
+        throw Exception("unreachable");
+    }
+  }
+
+  JsonRpcMetrics _wire2api_json_rpc_metrics(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return JsonRpcMetrics(
+      droppedMessages: _wire2api_u64(arr[0]),
+      bufferedMessages: _wire2api_u64(arr[1]),
+    );
+  }
+
+  LogEntry _wire2api_log_entry(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 4)
+      throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    return LogEntry(
+      timeMillis: _wire2api_i64(arr[0]),
+      level: _wire2api_i32(arr[1]),
+      tag: _wire2api_String(arr[2]),
+      msg: _wire2api_String(arr[3]),
+    );
+  }
+
+  String? _wire2api_opt_String(dynamic raw) {
+    return raw == null ? null : _wire2api_String(raw);
+  }
+
+  int? _wire2api_opt_box_autoadd_u64(dynamic raw) {
+    return raw == null ? null : _wire2api_box_autoadd_u64(raw);
+  }
+
+  SmoldotFlutterError _wire2api_smoldot_flutter_error(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return SmoldotFlutterError(
+      kind: _wire2api_smoldot_flutter_error_kind(arr[0]),
+      message: _wire2api_String(arr[1]),
+    );
+  }
+
+  SmoldotFlutterErrorKind _wire2api_smoldot_flutter_error_kind(dynamic raw) {
+    return SmoldotFlutterErrorKind.values[raw as int];
+  }
+
+  SubscriptionEvent _wire2api_subscription_event(dynamic raw) {
+    switch (raw[0]) {
+      case 0:
+        return SubscriptionEvent_Notification(
+          _wire2api_String(raw[1]),
+        );
+      case 1:
+        return SubscriptionEvent_Err(
+          _wire2api_box_autoadd_smoldot_flutter_error(raw[1]),
+        );
+      default:
+        throw Exception("unreachable");
+    }
+  }
+
+  int _wire2api_u16(dynamic raw) {
+    return castInt(raw);
+  }
+
+  int _wire2api_u32(dynamic raw) {
+    return raw as int;
+  }
+
+  int _wire2api_u64(dynamic raw) {
+    return castInt(raw);
+  }
+
+  int _wire2api_u8(dynamic raw) {
+    return raw as int;
+  }
+
+  Uint8List _wire2api_uint_8_list(dynamic raw) {
+    return raw as Uint8List;
   }
 
   void _wire2api_unit(dynamic raw) {
     return;
   }
+
+  WarpSyncPhase _wire2api_warp_sync_phase(dynamic raw) {
+    return WarpSyncPhase.values[raw as int];
+  }
 }
 
 // Section: api2wire
 
+@protected
+bool api2wire_bool(bool raw) {
+  return raw;
+}
+
+@protected
+int api2wire_i32(int raw) {
+  return raw;
+}
+
+@protected
+int api2wire_overflow_policy(OverflowPolicy raw) {
+  return api2wire_i32(raw.index);
+}
+
+@protected
+int api2wire_u16(int raw) {
+  return raw;
+}
+
+@protected
+int api2wire_u32(int raw) {
+  return raw;
+}
+
+@protected
+int api2wire_u64(int raw) {
+  return raw;
+}
+
 @protected
 int api2wire_u8(int raw) {
   return raw;
@@ -237,16 +1514,124 @@ class SmoldotFlutterPlatform extends FlutterRustBridgeBase<SmoldotFlutterWire> {
 
 // Section: api2wire
 
+  @protected
+  wire_ChainHandle api2wire_ChainHandle(ChainHandle raw) {
+    final ptr = inner.new_ChainHandle();
+    _api_fill_to_wire_ChainHandle(raw, ptr);
+    return ptr;
+  }
+
+  @protected
+  wire_LightClient api2wire_LightClient(LightClient raw) {
+    final ptr = inner.new_LightClient();
+    _api_fill_to_wire_LightClient(raw, ptr);
+    return ptr;
+  }
+
   @protected
   ffi.Pointer<wire_uint_8_list> api2wire_String(String raw) {
     return api2wire_uint_8_list(utf8.encoder.convert(raw));
   }
 
+  @protected
+  ffi.Pointer<wire_StringList> api2wire_StringList(List<String> raw) {
+    final ans = inner.new_StringList_0(raw.length);
+    for (var i = 0; i < raw.length; i++) {
+      ans.ref.ptr[i] = api2wire_String(raw[i]);
+    }
+    return ans;
+  }
+
+  @protected
+  ffi.Pointer<ffi.Bool> api2wire_box_autoadd_bool(bool raw) {
+    return inner.new_box_autoadd_bool_0(api2wire_bool(raw));
+  }
+
+  @protected
+  ffi.Pointer<wire_ChainOptions> api2wire_box_autoadd_chain_options(
+      ChainOptions raw) {
+    final ptr = inner.new_box_autoadd_chain_options_0();
+    _api_fill_to_wire_chain_options(raw, ptr.ref);
+    return ptr;
+  }
+
+  @protected
+  ffi.Pointer<wire_LightClientConfig> api2wire_box_autoadd_light_client_config(
+      LightClientConfig raw) {
+    final ptr = inner.new_box_autoadd_light_client_config_0();
+    _api_fill_to_wire_light_client_config(raw, ptr.ref);
+    return ptr;
+  }
+
+  @protected
+  ffi.Pointer<ffi.Int32> api2wire_box_autoadd_overflow_policy(
+      OverflowPolicy raw) {
+    return inner.new_box_autoadd_overflow_policy_0(
+        api2wire_overflow_policy(raw));
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint16> api2wire_box_autoadd_u16(int raw) {
+    return inner.new_box_autoadd_u16_0(api2wire_u16(raw));
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint32> api2wire_box_autoadd_u32(int raw) {
+    return inner.new_box_autoadd_u32_0(api2wire_u32(raw));
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint64> api2wire_box_autoadd_u64(int raw) {
+    return inner.new_box_autoadd_u64_0(api2wire_u64(raw));
+  }
+
   @protected
   ffi.Pointer<wire_uint_8_list> api2wire_opt_String(String? raw) {
     return raw == null ? ffi.nullptr : api2wire_String(raw);
   }
 
+  @protected
+  ffi.Pointer<ffi.Bool> api2wire_opt_box_autoadd_bool(bool? raw) {
+    return raw == null ? ffi.nullptr : api2wire_box_autoadd_bool(raw);
+  }
+
+  @protected
+  ffi.Pointer<wire_ChainOptions> api2wire_opt_box_autoadd_chain_options(
+      ChainOptions? raw) {
+    return raw == null ? ffi.nullptr : api2wire_box_autoadd_chain_options(raw);
+  }
+
+  @protected
+  ffi.Pointer<wire_LightClientConfig>
+      api2wire_opt_box_autoadd_light_client_config(LightClientConfig? raw) {
+    return raw == null
+        ? ffi.nullptr
+        : api2wire_box_autoadd_light_client_config(raw);
+  }
+
+  @protected
+  ffi.Pointer<ffi.Int32> api2wire_opt_box_autoadd_overflow_policy(
+      OverflowPolicy? raw) {
+    return raw == null
+        ? ffi.nullptr
+        : api2wire_box_autoadd_overflow_policy(raw);
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint16> api2wire_opt_box_autoadd_u16(int? raw) {
+    return raw == null ? ffi.nullptr : api2wire_box_autoadd_u16(raw);
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint32> api2wire_opt_box_autoadd_u32(int? raw) {
+    return raw == null ? ffi.nullptr : api2wire_box_autoadd_u32(raw);
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint64> api2wire_opt_box_autoadd_u64(int? raw) {
+    return raw == null ? ffi.nullptr : api2wire_box_autoadd_u64(raw);
+  }
+
   @protected
   ffi.Pointer<wire_uint_8_list> api2wire_uint_8_list(Uint8List raw) {
     final ans = inner.new_uint_8_list_0(raw.length);
@@ -255,7 +1640,59 @@ class SmoldotFlutterPlatform extends FlutterRustBridgeBase<SmoldotFlutterWire> {
   }
 // Section: finalizer
 
+  late final OpaqueTypeFinalizer _ChainHandleFinalizer =
+      OpaqueTypeFinalizer(inner._drop_opaque_ChainHandlePtr);
+  OpaqueTypeFinalizer get ChainHandleFinalizer => _ChainHandleFinalizer;
+  late final OpaqueTypeFinalizer _LightClientFinalizer =
+      OpaqueTypeFinalizer(inner._drop_opaque_LightClientPtr);
+  OpaqueTypeFinalizer get LightClientFinalizer => _LightClientFinalizer;
 // Section: api_fill_to_wire
+
+  void _api_fill_to_wire_ChainHandle(
+      ChainHandle apiObj, wire_ChainHandle wireObj) {
+    wireObj.ptr = apiObj.shareOrMove();
+  }
+
+  void _api_fill_to_wire_LightClient(
+      LightClient apiObj, wire_LightClient wireObj) {
+    wireObj.ptr = apiObj.shareOrMove();
+  }
+
+  void _api_fill_to_wire_box_autoadd_chain_options(
+      ChainOptions apiObj, ffi.Pointer<wire_ChainOptions> wireObj) {
+    _api_fill_to_wire_chain_options(apiObj, wireObj.ref);
+  }
+
+  void _api_fill_to_wire_box_autoadd_light_client_config(
+      LightClientConfig apiObj, ffi.Pointer<wire_LightClientConfig> wireObj) {
+    _api_fill_to_wire_light_client_config(apiObj, wireObj.ref);
+  }
+
+  void _api_fill_to_wire_chain_options(
+      ChainOptions apiObj, wire_ChainOptions wireObj) {
+    wireObj.max_pending_requests =
+        api2wire_opt_box_autoadd_u32(apiObj.maxPendingRequests);
+    wireObj.max_subscriptions =
+        api2wire_opt_box_autoadd_u32(apiObj.maxSubscriptions);
+    wireObj.disable_json_rpc =
+        api2wire_opt_box_autoadd_bool(apiObj.disableJsonRpc);
+    wireObj.listener_buffer_size =
+        api2wire_opt_box_autoadd_u32(apiObj.listenerBufferSize);
+    wireObj.overflow_policy =
+        api2wire_opt_box_autoadd_overflow_policy(apiObj.overflowPolicy);
+  }
+
+  void _api_fill_to_wire_light_client_config(
+      LightClientConfig apiObj, wire_LightClientConfig wireObj) {
+    wireObj.client_name = api2wire_opt_String(apiObj.clientName);
+    wireObj.client_version = api2wire_opt_String(apiObj.clientVersion);
+    wireObj.allow_tcp = api2wire_opt_box_autoadd_bool(apiObj.allowTcp);
+    wireObj.allow_websocket =
+        api2wire_opt_box_autoadd_bool(apiObj.allowWebsocket);
+    wireObj.database_dir = api2wire_opt_String(apiObj.databaseDir);
+    wireObj.database_snapshot_interval_secs =
+        api2wire_opt_box_autoadd_u32(apiObj.databaseSnapshotIntervalSecs);
+  }
 }
 
 // ignore_for_file: camel_case_types, non_constant_identifier_names, avoid_positional_boolean_parameters, annotate_overrides, constant_identifier_names
@@ -370,105 +1807,677 @@ class SmoldotFlutterWire implements FlutterRustBridgeWireBase {
 
   void wire_init_light_client(
     int port_,
+    ffi.Pointer<wire_LightClientConfig> config,
   ) {
     return _wire_init_light_client(
       port_,
+      config,
     );
   }
 
-  late final _wire_init_light_clientPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
-          'wire_init_light_client');
-  late final _wire_init_light_client =
-      _wire_init_light_clientPtr.asFunction<void Function(int)>();
+  late final _wire_init_light_clientPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64,
+              ffi.Pointer<wire_LightClientConfig>)>>('wire_init_light_client');
+  late final _wire_init_light_client = _wire_init_light_clientPtr
+      .asFunction<void Function(int, ffi.Pointer<wire_LightClientConfig>)>();
 
-  void wire_start_chain_sync(
+  void wire_create_light_client(
     int port_,
-    ffi.Pointer<wire_uint_8_list> chain_name,
-    ffi.Pointer<wire_uint_8_list> chain_spec,
-    ffi.Pointer<wire_uint_8_list> database,
-    ffi.Pointer<wire_uint_8_list> relay_chain,
+    ffi.Pointer<wire_LightClientConfig> config,
   ) {
-    return _wire_start_chain_sync(
+    return _wire_create_light_client(
       port_,
-      chain_name,
-      chain_spec,
-      database,
-      relay_chain,
+      config,
     );
   }
 
-  late final _wire_start_chain_syncPtr = _lookup<
+  late final _wire_create_light_clientPtr = _lookup<
       ffi.NativeFunction<
-          ffi.Void Function(
-              ffi.Int64,
-              ffi.Pointer<wire_uint_8_list>,
-              ffi.Pointer<wire_uint_8_list>,
-              ffi.Pointer<wire_uint_8_list>,
-              ffi.Pointer<wire_uint_8_list>)>>('wire_start_chain_sync');
-  late final _wire_start_chain_sync = _wire_start_chain_syncPtr.asFunction<
-      void Function(
-          int,
-          ffi.Pointer<wire_uint_8_list>,
-          ffi.Pointer<wire_uint_8_list>,
-          ffi.Pointer<wire_uint_8_list>,
-          ffi.Pointer<wire_uint_8_list>)>();
+          ffi.Void Function(ffi.Int64,
+              ffi.Pointer<wire_LightClientConfig>)>>('wire_create_light_client');
+  late final _wire_create_light_client = _wire_create_light_clientPtr
+      .asFunction<void Function(int, ffi.Pointer<wire_LightClientConfig>)>();
 
-  void wire_stop_chain_sync(
+  void wire_shutdown_light_client(
     int port_,
-    ffi.Pointer<wire_uint_8_list> chain_name,
   ) {
-    return _wire_stop_chain_sync(
+    return _wire_shutdown_light_client(
       port_,
-      chain_name,
     );
   }
 
-  late final _wire_stop_chain_syncPtr = _lookup<
-      ffi.NativeFunction<
-          ffi.Void Function(ffi.Int64,
-              ffi.Pointer<wire_uint_8_list>)>>('wire_stop_chain_sync');
-  late final _wire_stop_chain_sync = _wire_stop_chain_syncPtr
-      .asFunction<void Function(int, ffi.Pointer<wire_uint_8_list>)>();
+  late final _wire_shutdown_light_clientPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'wire_shutdown_light_client');
+  late final _wire_shutdown_light_client =
+      _wire_shutdown_light_clientPtr.asFunction<void Function(int)>();
 
-  void wire_send_json_rpc_request(
+  void wire_light_client_shutdown(
     int port_,
-    ffi.Pointer<wire_uint_8_list> chain_name,
-    ffi.Pointer<wire_uint_8_list> req,
+    wire_LightClient client,
   ) {
-    return _wire_send_json_rpc_request(
+    return _wire_light_client_shutdown(
+      port_,
+      client,
+    );
+  }
+
+  late final _wire_light_client_shutdownPtr = _lookup<
+          ffi.NativeFunction<ffi.Void Function(ffi.Int64, wire_LightClient)>>(
+      'wire_light_client_shutdown');
+  late final _wire_light_client_shutdown = _wire_light_client_shutdownPtr
+      .asFunction<void Function(int, wire_LightClient)>();
+
+  void wire_throttle_light_client(
+    int port_,
+    bool throttled,
+  ) {
+    return _wire_throttle_light_client(
+      port_,
+      throttled,
+    );
+  }
+
+  late final _wire_throttle_light_clientPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Bool)>>(
+          'wire_throttle_light_client');
+  late final _wire_throttle_light_client = _wire_throttle_light_clientPtr
+      .asFunction<void Function(int, bool)>();
+
+  void wire_light_client_throttle(
+    int port_,
+    wire_LightClient client,
+    bool throttled,
+  ) {
+    return _wire_light_client_throttle(
+      port_,
+      client,
+      throttled,
+    );
+  }
+
+  late final _wire_light_client_throttlePtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, wire_LightClient,
+              ffi.Bool)>>('wire_light_client_throttle');
+  late final _wire_light_client_throttle = _wire_light_client_throttlePtr
+      .asFunction<void Function(int, wire_LightClient, bool)>();
+
+  void wire_start_chain_sync(
+    int port_,
+    ffi.Pointer<wire_uint_8_list> chain_name,
+    ffi.Pointer<wire_uint_8_list> chain_spec,
+    ffi.Pointer<wire_uint_8_list> database,
+    ffi.Pointer<wire_StringList> relay_chains,
+    ffi.Pointer<wire_ChainOptions> options,
+  ) {
+    return _wire_start_chain_sync(
+      port_,
+      chain_name,
+      chain_spec,
+      database,
+      relay_chains,
+      options,
+    );
+  }
+
+  late final _wire_start_chain_syncPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(
+              ffi.Int64,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_StringList>,
+              ffi.Pointer<wire_ChainOptions>)>>('wire_start_chain_sync');
+  late final _wire_start_chain_sync = _wire_start_chain_syncPtr.asFunction<
+      void Function(
+          int,
+          ffi.Pointer<wire_uint_8_list>,
+          ffi.Pointer<wire_uint_8_list>,
+          ffi.Pointer<wire_uint_8_list>,
+          ffi.Pointer<wire_StringList>,
+          ffi.Pointer<wire_ChainOptions>)>();
+
+  void wire_light_client_start_chain_sync(
+    int port_,
+    wire_LightClient client,
+    ffi.Pointer<wire_uint_8_list> chain_name,
+    ffi.Pointer<wire_uint_8_list> chain_spec,
+    ffi.Pointer<wire_uint_8_list> database,
+    ffi.Pointer<wire_StringList> relay_chains,
+    ffi.Pointer<wire_ChainOptions> options,
+  ) {
+    return _wire_light_client_start_chain_sync(
       port_,
+      client,
       chain_name,
+      chain_spec,
+      database,
+      relay_chains,
+      options,
+    );
+  }
+
+  late final _wire_light_client_start_chain_syncPtr = _lookup<
+          ffi.NativeFunction<
+              ffi.Void Function(
+                  ffi.Int64,
+                  wire_LightClient,
+                  ffi.Pointer<wire_uint_8_list>,
+                  ffi.Pointer<wire_uint_8_list>,
+                  ffi.Pointer<wire_uint_8_list>,
+                  ffi.Pointer<wire_StringList>,
+                  ffi.Pointer<wire_ChainOptions>)>>(
+      'wire_light_client_start_chain_sync');
+  late final _wire_light_client_start_chain_sync =
+      _wire_light_client_start_chain_syncPtr.asFunction<
+          void Function(
+              int,
+              wire_LightClient,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_StringList>,
+              ffi.Pointer<wire_ChainOptions>)>();
+
+  void wire_restart_chain_sync(
+    int port_,
+    wire_ChainHandle chain,
+    ffi.Pointer<wire_uint_8_list> chain_spec,
+    ffi.Pointer<wire_uint_8_list> database,
+    ffi.Pointer<wire_StringList> relay_chains,
+    ffi.Pointer<wire_ChainOptions> options,
+  ) {
+    return _wire_restart_chain_sync(
+      port_,
+      chain,
+      chain_spec,
+      database,
+      relay_chains,
+      options,
+    );
+  }
+
+  late final _wire_restart_chain_syncPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(
+              ffi.Int64,
+              wire_ChainHandle,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_StringList>,
+              ffi.Pointer<wire_ChainOptions>)>>('wire_restart_chain_sync');
+  late final _wire_restart_chain_sync = _wire_restart_chain_syncPtr.asFunction<
+      void Function(
+          int,
+          wire_ChainHandle,
+          ffi.Pointer<wire_uint_8_list>,
+          ffi.Pointer<wire_uint_8_list>,
+          ffi.Pointer<wire_StringList>,
+          ffi.Pointer<wire_ChainOptions>)>();
+
+  void wire_export_chain_database(
+    int port_,
+    wire_ChainHandle chain,
+    ffi.Pointer<ffi.Uint64> max_size_bytes,
+  ) {
+    return _wire_export_chain_database(
+      port_,
+      chain,
+      max_size_bytes,
+    );
+  }
+
+  late final _wire_export_chain_databasePtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, wire_ChainHandle,
+              ffi.Pointer<ffi.Uint64>)>>('wire_export_chain_database');
+  late final _wire_export_chain_database =
+      _wire_export_chain_databasePtr.asFunction<
+          void Function(int, wire_ChainHandle, ffi.Pointer<ffi.Uint64>)>();
+
+  void wire_import_chain_database(
+    int port_,
+    wire_ChainHandle chain,
+    ffi.Pointer<wire_uint_8_list> database,
+  ) {
+    return _wire_import_chain_database(
+      port_,
+      chain,
+      database,
+    );
+  }
+
+  late final _wire_import_chain_databasePtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, wire_ChainHandle,
+              ffi.Pointer<wire_uint_8_list>)>>('wire_import_chain_database');
+  late final _wire_import_chain_database =
+      _wire_import_chain_databasePtr.asFunction<
+          void Function(
+              int, wire_ChainHandle, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_stop_chain_sync(
+    int port_,
+    wire_ChainHandle chain,
+    ffi.Pointer<ffi.Uint32> save_timeout_millis,
+  ) {
+    return _wire_stop_chain_sync(
+      port_,
+      chain,
+      save_timeout_millis,
+    );
+  }
+
+  late final _wire_stop_chain_syncPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, wire_ChainHandle,
+              ffi.Pointer<ffi.Uint32>)>>('wire_stop_chain_sync');
+  late final _wire_stop_chain_sync = _wire_stop_chain_syncPtr.asFunction<
+      void Function(int, wire_ChainHandle, ffi.Pointer<ffi.Uint32>)>();
+
+  void wire_suspend_all_chains(
+    int port_,
+    int save_timeout_millis,
+  ) {
+    return _wire_suspend_all_chains(
+      port_,
+      save_timeout_millis,
+    );
+  }
+
+  late final _wire_suspend_all_chainsPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_suspend_all_chains');
+  late final _wire_suspend_all_chains =
+      _wire_suspend_all_chainsPtr.asFunction<void Function(int, int)>();
+
+  void wire_light_client_suspend_all_chains(
+    int port_,
+    wire_LightClient client,
+    int save_timeout_millis,
+  ) {
+    return _wire_light_client_suspend_all_chains(
+      port_,
+      client,
+      save_timeout_millis,
+    );
+  }
+
+  late final _wire_light_client_suspend_all_chainsPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, wire_LightClient,
+              ffi.Uint32)>>('wire_light_client_suspend_all_chains');
+  late final _wire_light_client_suspend_all_chains =
+      _wire_light_client_suspend_all_chainsPtr
+          .asFunction<void Function(int, wire_LightClient, int)>();
+
+  void wire_send_json_rpc_request(
+    int port_,
+    wire_ChainHandle chain,
+    ffi.Pointer<wire_uint_8_list> req,
+  ) {
+    return _wire_send_json_rpc_request(
+      port_,
+      chain,
       req,
     );
   }
 
   late final _wire_send_json_rpc_requestPtr = _lookup<
       ffi.NativeFunction<
-          ffi.Void Function(ffi.Int64, ffi.Pointer<wire_uint_8_list>,
+          ffi.Void Function(ffi.Int64, wire_ChainHandle,
               ffi.Pointer<wire_uint_8_list>)>>('wire_send_json_rpc_request');
   late final _wire_send_json_rpc_request =
       _wire_send_json_rpc_requestPtr.asFunction<
-          void Function(int, ffi.Pointer<wire_uint_8_list>,
-              ffi.Pointer<wire_uint_8_list>)>();
+          void Function(
+              int, wire_ChainHandle, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_json_rpc_call(
+    int port_,
+    wire_ChainHandle chain,
+    ffi.Pointer<wire_uint_8_list> method,
+    ffi.Pointer<wire_uint_8_list> params,
+  ) {
+    return _wire_json_rpc_call(
+      port_,
+      chain,
+      method,
+      params,
+    );
+  }
+
+  late final _wire_json_rpc_callPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(
+              ffi.Int64,
+              wire_ChainHandle,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>)>>('wire_json_rpc_call');
+  late final _wire_json_rpc_call = _wire_json_rpc_callPtr.asFunction<
+      void Function(int, wire_ChainHandle, ffi.Pointer<wire_uint_8_list>,
+          ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_subscribe(
+    int port_,
+    wire_ChainHandle chain,
+    ffi.Pointer<wire_uint_8_list> method,
+    ffi.Pointer<wire_uint_8_list> params,
+    ffi.Pointer<wire_uint_8_list> unsubscribe_method,
+  ) {
+    return _wire_subscribe(
+      port_,
+      chain,
+      method,
+      params,
+      unsubscribe_method,
+    );
+  }
+
+  late final _wire_subscribePtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(
+              ffi.Int64,
+              wire_ChainHandle,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>,
+              ffi.Pointer<wire_uint_8_list>)>>('wire_subscribe');
+  late final _wire_subscribe = _wire_subscribePtr.asFunction<
+      void Function(int, wire_ChainHandle, ffi.Pointer<wire_uint_8_list>,
+          ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>)>();
 
   void wire_listen_json_rpc_responses(
     int port_,
-    ffi.Pointer<wire_uint_8_list> chain_name,
+    wire_ChainHandle chain,
+    ffi.Pointer<ffi.Uint32> replay,
   ) {
     return _wire_listen_json_rpc_responses(
       port_,
-      chain_name,
+      chain,
+      replay,
     );
   }
 
   late final _wire_listen_json_rpc_responsesPtr = _lookup<
-          ffi.NativeFunction<
-              ffi.Void Function(ffi.Int64, ffi.Pointer<wire_uint_8_list>)>>(
-      'wire_listen_json_rpc_responses');
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, wire_ChainHandle,
+              ffi.Pointer<ffi.Uint32>)>>('wire_listen_json_rpc_responses');
   late final _wire_listen_json_rpc_responses =
-      _wire_listen_json_rpc_responsesPtr
-          .asFunction<void Function(int, ffi.Pointer<wire_uint_8_list>)>();
+      _wire_listen_json_rpc_responsesPtr.asFunction<
+          void Function(int, wire_ChainHandle, ffi.Pointer<ffi.Uint32>)>();
+
+  void wire_ack_json_rpc_responses(
+    int port_,
+    wire_ChainHandle chain,
+    int count,
+  ) {
+    return _wire_ack_json_rpc_responses(
+      port_,
+      chain,
+      count,
+    );
+  }
+
+  late final _wire_ack_json_rpc_responsesPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, wire_ChainHandle,
+              ffi.Uint32)>>('wire_ack_json_rpc_responses');
+  late final _wire_ack_json_rpc_responses = _wire_ack_json_rpc_responsesPtr
+      .asFunction<void Function(int, wire_ChainHandle, int)>();
+
+  void wire_unlisten_json_rpc_responses(
+    int port_,
+    wire_ChainHandle chain,
+  ) {
+    return _wire_unlisten_json_rpc_responses(
+      port_,
+      chain,
+    );
+  }
+
+  late final _wire_unlisten_json_rpc_responsesPtr = _lookup<
+          ffi.NativeFunction<ffi.Void Function(ffi.Int64, wire_ChainHandle)>>(
+      'wire_unlisten_json_rpc_responses');
+  late final _wire_unlisten_json_rpc_responses =
+      _wire_unlisten_json_rpc_responsesPtr
+          .asFunction<void Function(int, wire_ChainHandle)>();
+
+  void wire_json_rpc_metrics(
+    int port_,
+    wire_ChainHandle chain,
+  ) {
+    return _wire_json_rpc_metrics(
+      port_,
+      chain,
+    );
+  }
+
+  late final _wire_json_rpc_metricsPtr = _lookup<
+          ffi.NativeFunction<ffi.Void Function(ffi.Int64, wire_ChainHandle)>>(
+      'wire_json_rpc_metrics');
+  late final _wire_json_rpc_metrics = _wire_json_rpc_metricsPtr
+      .asFunction<void Function(int, wire_ChainHandle)>();
+
+  void wire_watch_chain_status(
+    int port_,
+    wire_ChainHandle chain,
+  ) {
+    return _wire_watch_chain_status(
+      port_,
+      chain,
+    );
+  }
+
+  late final _wire_watch_chain_statusPtr = _lookup<
+          ffi.NativeFunction<ffi.Void Function(ffi.Int64, wire_ChainHandle)>>(
+      'wire_watch_chain_status');
+  late final _wire_watch_chain_status = _wire_watch_chain_statusPtr
+      .asFunction<void Function(int, wire_ChainHandle)>();
+
+  void wire_start_json_rpc_server(
+    int port_,
+    ffi.Pointer<ffi.Uint16> port,
+    ffi.Pointer<wire_StringList> allowed_origins,
+  ) {
+    return _wire_start_json_rpc_server(
+      port_,
+      port,
+      allowed_origins,
+    );
+  }
+
+  late final _wire_start_json_rpc_serverPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Void Function(ffi.Int64, ffi.Pointer<ffi.Uint16>,
+              ffi.Pointer<wire_StringList>)>>('wire_start_json_rpc_server');
+  late final _wire_start_json_rpc_server =
+      _wire_start_json_rpc_serverPtr.asFunction<
+          void Function(
+              int, ffi.Pointer<ffi.Uint16>, ffi.Pointer<wire_StringList>)>();
+
+  void wire_light_client_start_json_rpc_server(
+    int port_,
+    wire_LightClient client,
+    ffi.Pointer<ffi.Uint16> port,
+    ffi.Pointer<wire_StringList> allowed_origins,
+  ) {
+    return _wire_light_client_start_json_rpc_server(
+      port_,
+      client,
+      port,
+      allowed_origins,
+    );
+  }
+
+  late final _wire_light_client_start_json_rpc_serverPtr = _lookup<
+          ffi.NativeFunction<
+              ffi.Void Function(ffi.Int64, wire_LightClient,
+                  ffi.Pointer<ffi.Uint16>, ffi.Pointer<wire_StringList>)>>(
+      'wire_light_client_start_json_rpc_server');
+  late final _wire_light_client_start_json_rpc_server =
+      _wire_light_client_start_json_rpc_serverPtr.asFunction<
+          void Function(int, wire_LightClient, ffi.Pointer<ffi.Uint16>,
+              ffi.Pointer<wire_StringList>)>();
+
+  void wire_stop_json_rpc_server(
+    int port_,
+  ) {
+    return _wire_stop_json_rpc_server(
+      port_,
+    );
+  }
+
+  late final _wire_stop_json_rpc_serverPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'wire_stop_json_rpc_server');
+  late final _wire_stop_json_rpc_server =
+      _wire_stop_json_rpc_serverPtr.asFunction<void Function(int)>();
+
+  void wire_light_client_stop_json_rpc_server(
+    int port_,
+    wire_LightClient client,
+  ) {
+    return _wire_light_client_stop_json_rpc_server(
+      port_,
+      client,
+    );
+  }
+
+  late final _wire_light_client_stop_json_rpc_serverPtr = _lookup<
+          ffi.NativeFunction<ffi.Void Function(ffi.Int64, wire_LightClient)>>(
+      'wire_light_client_stop_json_rpc_server');
+  late final _wire_light_client_stop_json_rpc_server =
+      _wire_light_client_stop_json_rpc_serverPtr
+          .asFunction<void Function(int, wire_LightClient)>();
+
+  wire_ChainHandle new_ChainHandle() {
+    return _new_ChainHandle();
+  }
+
+  late final _new_ChainHandlePtr =
+      _lookup<ffi.NativeFunction<wire_ChainHandle Function()>>(
+          'new_ChainHandle');
+  late final _new_ChainHandle =
+      _new_ChainHandlePtr.asFunction<wire_ChainHandle Function()>();
+
+  wire_LightClient new_LightClient() {
+    return _new_LightClient();
+  }
+
+  late final _new_LightClientPtr =
+      _lookup<ffi.NativeFunction<wire_LightClient Function()>>(
+          'new_LightClient');
+  late final _new_LightClient =
+      _new_LightClientPtr.asFunction<wire_LightClient Function()>();
+
+  ffi.Pointer<wire_StringList> new_StringList_0(
+    int len,
+  ) {
+    return _new_StringList_0(
+      len,
+    );
+  }
+
+  late final _new_StringList_0Ptr = _lookup<
+          ffi.NativeFunction<ffi.Pointer<wire_StringList> Function(ffi.Int32)>>(
+      'new_StringList_0');
+  late final _new_StringList_0 = _new_StringList_0Ptr
+      .asFunction<ffi.Pointer<wire_StringList> Function(int)>();
+
+  ffi.Pointer<ffi.Bool> new_box_autoadd_bool_0(
+    bool value,
+  ) {
+    return _new_box_autoadd_bool_0(
+      value,
+    );
+  }
+
+  late final _new_box_autoadd_bool_0Ptr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<ffi.Bool> Function(ffi.Bool)>>(
+          'new_box_autoadd_bool_0');
+  late final _new_box_autoadd_bool_0 = _new_box_autoadd_bool_0Ptr
+      .asFunction<ffi.Pointer<ffi.Bool> Function(bool)>();
+
+  ffi.Pointer<wire_ChainOptions> new_box_autoadd_chain_options_0() {
+    return _new_box_autoadd_chain_options_0();
+  }
+
+  late final _new_box_autoadd_chain_options_0Ptr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<wire_ChainOptions> Function()>>(
+          'new_box_autoadd_chain_options_0');
+  late final _new_box_autoadd_chain_options_0 =
+      _new_box_autoadd_chain_options_0Ptr
+          .asFunction<ffi.Pointer<wire_ChainOptions> Function()>();
+
+  ffi.Pointer<wire_LightClientConfig> new_box_autoadd_light_client_config_0() {
+    return _new_box_autoadd_light_client_config_0();
+  }
+
+  late final _new_box_autoadd_light_client_config_0Ptr = _lookup<
+          ffi.NativeFunction<ffi.Pointer<wire_LightClientConfig> Function()>>(
+      'new_box_autoadd_light_client_config_0');
+  late final _new_box_autoadd_light_client_config_0 =
+      _new_box_autoadd_light_client_config_0Ptr
+          .asFunction<ffi.Pointer<wire_LightClientConfig> Function()>();
+
+  ffi.Pointer<ffi.Int32> new_box_autoadd_overflow_policy_0(
+    int value,
+  ) {
+    return _new_box_autoadd_overflow_policy_0(
+      value,
+    );
+  }
+
+  late final _new_box_autoadd_overflow_policy_0Ptr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<ffi.Int32> Function(ffi.Int32)>>(
+          'new_box_autoadd_overflow_policy_0');
+  late final _new_box_autoadd_overflow_policy_0 =
+      _new_box_autoadd_overflow_policy_0Ptr
+          .asFunction<ffi.Pointer<ffi.Int32> Function(int)>();
+
+  ffi.Pointer<ffi.Uint16> new_box_autoadd_u16_0(
+    int value,
+  ) {
+    return _new_box_autoadd_u16_0(
+      value,
+    );
+  }
+
+  late final _new_box_autoadd_u16_0Ptr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<ffi.Uint16> Function(ffi.Uint16)>>(
+          'new_box_autoadd_u16_0');
+  late final _new_box_autoadd_u16_0 = _new_box_autoadd_u16_0Ptr
+      .asFunction<ffi.Pointer<ffi.Uint16> Function(int)>();
+
+  ffi.Pointer<ffi.Uint32> new_box_autoadd_u32_0(
+    int value,
+  ) {
+    return _new_box_autoadd_u32_0(
+      value,
+    );
+  }
+
+  late final _new_box_autoadd_u32_0Ptr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<ffi.Uint32> Function(ffi.Uint32)>>(
+          'new_box_autoadd_u32_0');
+  late final _new_box_autoadd_u32_0 = _new_box_autoadd_u32_0Ptr
+      .asFunction<ffi.Pointer<ffi.Uint32> Function(int)>();
+
+  ffi.Pointer<ffi.Uint64> new_box_autoadd_u64_0(
+    int value,
+  ) {
+    return _new_box_autoadd_u64_0(
+      value,
+    );
+  }
+
+  late final _new_box_autoadd_u64_0Ptr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<ffi.Uint64> Function(ffi.Uint64)>>(
+          'new_box_autoadd_u64_0');
+  late final _new_box_autoadd_u64_0 = _new_box_autoadd_u64_0Ptr
+      .asFunction<ffi.Pointer<ffi.Uint64> Function(int)>();
 
   ffi.Pointer<wire_uint_8_list> new_uint_8_list_0(
     int len,
@@ -485,6 +2494,64 @@ class SmoldotFlutterWire implements FlutterRustBridgeWireBase {
   late final _new_uint_8_list_0 = _new_uint_8_list_0Ptr
       .asFunction<ffi.Pointer<wire_uint_8_list> Function(int)>();
 
+  void drop_opaque_ChainHandle(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _drop_opaque_ChainHandle(
+      ptr,
+    );
+  }
+
+  late final _drop_opaque_ChainHandlePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
+          'drop_opaque_ChainHandle');
+  late final _drop_opaque_ChainHandle = _drop_opaque_ChainHandlePtr
+      .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
+
+  ffi.Pointer<ffi.Void> share_opaque_ChainHandle(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _share_opaque_ChainHandle(
+      ptr,
+    );
+  }
+
+  late final _share_opaque_ChainHandlePtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Pointer<ffi.Void> Function(
+              ffi.Pointer<ffi.Void>)>>('share_opaque_ChainHandle');
+  late final _share_opaque_ChainHandle = _share_opaque_ChainHandlePtr
+      .asFunction<ffi.Pointer<ffi.Void> Function(ffi.Pointer<ffi.Void>)>();
+
+  void drop_opaque_LightClient(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _drop_opaque_LightClient(
+      ptr,
+    );
+  }
+
+  late final _drop_opaque_LightClientPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Pointer<ffi.Void>)>>(
+          'drop_opaque_LightClient');
+  late final _drop_opaque_LightClient = _drop_opaque_LightClientPtr
+      .asFunction<void Function(ffi.Pointer<ffi.Void>)>();
+
+  ffi.Pointer<ffi.Void> share_opaque_LightClient(
+    ffi.Pointer<ffi.Void> ptr,
+  ) {
+    return _share_opaque_LightClient(
+      ptr,
+    );
+  }
+
+  late final _share_opaque_LightClientPtr = _lookup<
+      ffi.NativeFunction<
+          ffi.Pointer<ffi.Void> Function(
+              ffi.Pointer<ffi.Void>)>>('share_opaque_LightClient');
+  late final _share_opaque_LightClient = _share_opaque_LightClientPtr
+      .asFunction<ffi.Pointer<ffi.Void> Function(ffi.Pointer<ffi.Void>)>();
+
   void free_WireSyncReturn(
     WireSyncReturn ptr,
   ) {
@@ -502,6 +2569,47 @@ class SmoldotFlutterWire implements FlutterRustBridgeWireBase {
 
 final class _Dart_Handle extends ffi.Opaque {}
 
+final class wire_ChainHandle extends ffi.Struct {
+  external ffi.Pointer<ffi.Void> ptr;
+}
+
+final class wire_ChainOptions extends ffi.Struct {
+  external ffi.Pointer<ffi.Uint32> max_pending_requests;
+
+  external ffi.Pointer<ffi.Uint32> max_subscriptions;
+
+  external ffi.Pointer<ffi.Bool> disable_json_rpc;
+
+  external ffi.Pointer<ffi.Uint32> listener_buffer_size;
+
+  external ffi.Pointer<ffi.Int32> overflow_policy;
+}
+
+final class wire_LightClient extends ffi.Struct {
+  external ffi.Pointer<ffi.Void> ptr;
+}
+
+final class wire_LightClientConfig extends ffi.Struct {
+  external ffi.Pointer<wire_uint_8_list> client_name;
+
+  external ffi.Pointer<wire_uint_8_list> client_version;
+
+  external ffi.Pointer<ffi.Bool> allow_tcp;
+
+  external ffi.Pointer<ffi.Bool> allow_websocket;
+
+  external ffi.Pointer<wire_uint_8_list> database_dir;
+
+  external ffi.Pointer<ffi.Uint32> database_snapshot_interval_secs;
+}
+
+final class wire_StringList extends ffi.Struct {
+  external ffi.Pointer<ffi.Pointer<wire_uint_8_list>> ptr;
+
+  @ffi.Int32()
+  external int len;
+}
+
 final class wire_uint_8_list extends ffi.Struct {
   external ffi.Pointer<ffi.Uint8> ptr;
 
@@ -0,0 +1,1021 @@
+// coverage:ignore-file
+// GENERATED CODE - DO NOT MODIFY BY HAND
+// ignore_for_file: type=lint
+// ignore_for_file: unused_element, deprecated_member_use, deprecated_member_use_from_same_package, use_function_type_syntax_for_parameters, unnecessary_const, avoid_init_to_null, invalid_override_different_default_values_named, prefer_expression_function_bodies, annotate_overrides, invalid_annotation_target, unnecessary_question_mark
+
+part of 'bridge_generated.dart';
+
+// **************************************************************************
+// FreezedGenerator
+// **************************************************************************
+
+T _$identity<T>(T value) => value;
+
+final _privateConstructorUsedError = UnsupportedError(
+    'It seems like you constructed your class using `MyClass._()`. This constructor is only meant to be used by freezed and you are not supposed to need it nor use it.\nPlease check the documentation here for more information: https://github.com/rrousselGit/freezed#custom-getters-and-methods');
+
+/// @nodoc
+mixin _$ChainStatusEvent {
+  Object get field0 => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(ChainStatus field0) status,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(ChainStatus field0)? status,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(ChainStatus field0)? status,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(ChainStatusEvent_Status value) status,
+    required TResult Function(ChainStatusEvent_Err value) err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(ChainStatusEvent_Status value)? status,
+    TResult? Function(ChainStatusEvent_Err value)? err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(ChainStatusEvent_Status value)? status,
+    TResult Function(ChainStatusEvent_Err value)? err,
+    required TResult orElse(),
+  }) =>
+      throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+abstract class $ChainStatusEventCopyWith<$Res> {
+  factory $ChainStatusEventCopyWith(
+          ChainStatusEvent value, $Res Function(ChainStatusEvent) then) =
+      _$ChainStatusEventCopyWithImpl<$Res, ChainStatusEvent>;
+}
+
+/// @nodoc
+class _$ChainStatusEventCopyWithImpl<$Res, $Val extends ChainStatusEvent>
+    implements $ChainStatusEventCopyWith<$Res> {
+  _$ChainStatusEventCopyWithImpl(this._value, this._then);
+
+  // ignore: unused_field
+  final $Val _value;
+  // ignore: unused_field
+  final $Res Function($Val) _then;
+}
+
+/// @nodoc
+abstract class _$$ChainStatusEvent_StatusCopyWith<$Res> {
+  factory _$$ChainStatusEvent_StatusCopyWith(_$ChainStatusEvent_Status value,
+          $Res Function(_$ChainStatusEvent_Status) then) =
+      __$$ChainStatusEvent_StatusCopyWithImpl<$Res>;
+  @useResult
+  $Res call({ChainStatus field0});
+}
+
+/// @nodoc
+class __$$ChainStatusEvent_StatusCopyWithImpl<$Res>
+    extends _$ChainStatusEventCopyWithImpl<$Res, _$ChainStatusEvent_Status>
+    implements _$$ChainStatusEvent_StatusCopyWith<$Res> {
+  __$$ChainStatusEvent_StatusCopyWithImpl(_$ChainStatusEvent_Status _value,
+      $Res Function(_$ChainStatusEvent_Status) _then)
+      : super(_value, _then);
+
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({
+    Object? field0 = null,
+  }) {
+    return _then(_$ChainStatusEvent_Status(
+      null == field0
+          ? _value.field0
+          : field0 // ignore: cast_nullable_to_non_nullable
+              as ChainStatus,
+    ));
+  }
+}
+
+/// @nodoc
+
+class _$ChainStatusEvent_Status implements ChainStatusEvent_Status {
+  const _$ChainStatusEvent_Status(this.field0);
+
+  @override
+  final ChainStatus field0;
+
+  @override
+  String toString() {
+    return 'ChainStatusEvent.status(field0: $field0)';
+  }
+
+  @override
+  bool operator ==(dynamic other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$ChainStatusEvent_Status &&
+            (identical(other.field0, field0) || other.field0 == field0));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, field0);
+
+  @JsonKey(ignore: true)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$ChainStatusEvent_StatusCopyWith<_$ChainStatusEvent_Status> get copyWith =>
+      __$$ChainStatusEvent_StatusCopyWithImpl<_$ChainStatusEvent_Status>(
+          this, _$identity);
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(ChainStatus field0) status,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) {
+    return status(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(ChainStatus field0)? status,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) {
+    return status?.call(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(ChainStatus field0)? status,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) {
+    if (status != null) {
+      return status(field0);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(ChainStatusEvent_Status value) status,
+    required TResult Function(ChainStatusEvent_Err value) err,
+  }) {
+    return status(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(ChainStatusEvent_Status value)? status,
+    TResult? Function(ChainStatusEvent_Err value)? err,
+  }) {
+    return status?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(ChainStatusEvent_Status value)? status,
+    TResult Function(ChainStatusEvent_Err value)? err,
+    required TResult orElse(),
+  }) {
+    if (status != null) {
+      return status(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class ChainStatusEvent_Status implements ChainStatusEvent {
+  const factory ChainStatusEvent_Status(final ChainStatus field0) =
+      _$ChainStatusEvent_Status;
+
+  @override
+  ChainStatus get field0;
+  @JsonKey(ignore: true)
+  _$$ChainStatusEvent_StatusCopyWith<_$ChainStatusEvent_Status> get copyWith =>
+      throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+abstract class _$$ChainStatusEvent_ErrCopyWith<$Res> {
+  factory _$$ChainStatusEvent_ErrCopyWith(_$ChainStatusEvent_Err value,
+          $Res Function(_$ChainStatusEvent_Err) then) =
+      __$$ChainStatusEvent_ErrCopyWithImpl<$Res>;
+  @useResult
+  $Res call({SmoldotFlutterError field0});
+}
+
+/// @nodoc
+class __$$ChainStatusEvent_ErrCopyWithImpl<$Res>
+    extends _$ChainStatusEventCopyWithImpl<$Res, _$ChainStatusEvent_Err>
+    implements _$$ChainStatusEvent_ErrCopyWith<$Res> {
+  __$$ChainStatusEvent_ErrCopyWithImpl(_$ChainStatusEvent_Err _value,
+      $Res Function(_$ChainStatusEvent_Err) _then)
+      : super(_value, _then);
+
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({
+    Object? field0 = null,
+  }) {
+    return _then(_$ChainStatusEvent_Err(
+      null == field0
+          ? _value.field0
+          : field0 // ignore: cast_nullable_to_non_nullable
+              as SmoldotFlutterError,
+    ));
+  }
+}
+
+/// @nodoc
+
+class _$ChainStatusEvent_Err implements ChainStatusEvent_Err {
+  const _$ChainStatusEvent_Err(this.field0);
+
+  @override
+  final SmoldotFlutterError field0;
+
+  @override
+  String toString() {
+    return 'ChainStatusEvent.err(field0: $field0)';
+  }
+
+  @override
+  bool operator ==(dynamic other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$ChainStatusEvent_Err &&
+            (identical(other.field0, field0) || other.field0 == field0));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, field0);
+
+  @JsonKey(ignore: true)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$ChainStatusEvent_ErrCopyWith<_$ChainStatusEvent_Err> get copyWith =>
+      __$$ChainStatusEvent_ErrCopyWithImpl<_$ChainStatusEvent_Err>(
+          this, _$identity);
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(ChainStatus field0) status,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) {
+    return err(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(ChainStatus field0)? status,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) {
+    return err?.call(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(ChainStatus field0)? status,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) {
+    if (err != null) {
+      return err(field0);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(ChainStatusEvent_Status value) status,
+    required TResult Function(ChainStatusEvent_Err value) err,
+  }) {
+    return err(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(ChainStatusEvent_Status value)? status,
+    TResult? Function(ChainStatusEvent_Err value)? err,
+  }) {
+    return err?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(ChainStatusEvent_Status value)? status,
+    TResult Function(ChainStatusEvent_Err value)? err,
+    required TResult orElse(),
+  }) {
+    if (err != null) {
+      return err(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class ChainStatusEvent_Err implements ChainStatusEvent {
+  const factory ChainStatusEvent_Err(final SmoldotFlutterError field0) =
+      _$ChainStatusEvent_Err;
+
+  @override
+  SmoldotFlutterError get field0;
+  @JsonKey(ignore: true)
+  _$$ChainStatusEvent_ErrCopyWith<_$ChainStatusEvent_Err> get copyWith =>
+      throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+mixin _$JsonRpcCallResult {
+  Object get field0 => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(String field0) ok,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(String field0)? ok,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(String field0)? ok,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(JsonRpcCallResult_Ok value) ok,
+    required TResult Function(JsonRpcCallResult_Err value) err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(JsonRpcCallResult_Ok value)? ok,
+    TResult? Function(JsonRpcCallResult_Err value)? err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(JsonRpcCallResult_Ok value)? ok,
+    TResult Function(JsonRpcCallResult_Err value)? err,
+    required TResult orElse(),
+  }) =>
+      throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+abstract class $JsonRpcCallResultCopyWith<$Res> {
+  factory $JsonRpcCallResultCopyWith(
+          JsonRpcCallResult value, $Res Function(JsonRpcCallResult) then) =
+      _$JsonRpcCallResultCopyWithImpl<$Res, JsonRpcCallResult>;
+}
+
+/// @nodoc
+class _$JsonRpcCallResultCopyWithImpl<$Res, $Val extends JsonRpcCallResult>
+    implements $JsonRpcCallResultCopyWith<$Res> {
+  _$JsonRpcCallResultCopyWithImpl(this._value, this._then);
+
+  // ignore: unused_field
+  final $Val _value;
+  // ignore: unused_field
+  final $Res Function($Val) _then;
+}
+
+/// @nodoc
+abstract class _$$JsonRpcCallResult_OkCopyWith<$Res> {
+  factory _$$JsonRpcCallResult_OkCopyWith(_$JsonRpcCallResult_Ok value,
+          $Res Function(_$JsonRpcCallResult_Ok) then) =
+      __$$JsonRpcCallResult_OkCopyWithImpl<$Res>;
+  @useResult
+  $Res call({String field0});
+}
+
+/// @nodoc
+class __$$JsonRpcCallResult_OkCopyWithImpl<$Res>
+    extends _$JsonRpcCallResultCopyWithImpl<$Res, _$JsonRpcCallResult_Ok>
+    implements _$$JsonRpcCallResult_OkCopyWith<$Res> {
+  __$$JsonRpcCallResult_OkCopyWithImpl(_$JsonRpcCallResult_Ok _value,
+      $Res Function(_$JsonRpcCallResult_Ok) _then)
+      : super(_value, _then);
+
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({
+    Object? field0 = null,
+  }) {
+    return _then(_$JsonRpcCallResult_Ok(
+      null == field0
+          ? _value.field0
+          : field0 // ignore: cast_nullable_to_non_nullable
+              as String,
+    ));
+  }
+}
+
+/// @nodoc
+
+class _$JsonRpcCallResult_Ok implements JsonRpcCallResult_Ok {
+  const _$JsonRpcCallResult_Ok(this.field0);
+
+  @override
+  final String field0;
+
+  @override
+  String toString() {
+    return 'JsonRpcCallResult.ok(field0: $field0)';
+  }
+
+  @override
+  bool operator ==(dynamic other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$JsonRpcCallResult_Ok &&
+            (identical(other.field0, field0) || other.field0 == field0));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, field0);
+
+  @JsonKey(ignore: true)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$JsonRpcCallResult_OkCopyWith<_$JsonRpcCallResult_Ok> get copyWith =>
+      __$$JsonRpcCallResult_OkCopyWithImpl<_$JsonRpcCallResult_Ok>(
+          this, _$identity);
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(String field0) ok,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) {
+    return ok(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(String field0)? ok,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) {
+    return ok?.call(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(String field0)? ok,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) {
+    if (ok != null) {
+      return ok(field0);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(JsonRpcCallResult_Ok value) ok,
+    required TResult Function(JsonRpcCallResult_Err value) err,
+  }) {
+    return ok(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(JsonRpcCallResult_Ok value)? ok,
+    TResult? Function(JsonRpcCallResult_Err value)? err,
+  }) {
+    return ok?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(JsonRpcCallResult_Ok value)? ok,
+    TResult Function(JsonRpcCallResult_Err value)? err,
+    required TResult orElse(),
+  }) {
+    if (ok != null) {
+      return ok(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class JsonRpcCallResult_Ok implements JsonRpcCallResult {
+  const factory JsonRpcCallResult_Ok(final String field0) =
+      _$JsonRpcCallResult_Ok;
+
+  @override
+  String get field0;
+  @JsonKey(ignore: true)
+  _$$JsonRpcCallResult_OkCopyWith<_$JsonRpcCallResult_Ok> get copyWith =>
+      throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+abstract class _$$JsonRpcCallResult_ErrCopyWith<$Res> {
+  factory _$$JsonRpcCallResult_ErrCopyWith(_$JsonRpcCallResult_Err value,
+          $Res Function(_$JsonRpcCallResult_Err) then) =
+      __$$JsonRpcCallResult_ErrCopyWithImpl<$Res>;
+  @useResult
+  $Res call({SmoldotFlutterError field0});
+}
+
+/// @nodoc
+class __$$JsonRpcCallResult_ErrCopyWithImpl<$Res>
+    extends _$JsonRpcCallResultCopyWithImpl<$Res, _$JsonRpcCallResult_Err>
+    implements _$$JsonRpcCallResult_ErrCopyWith<$Res> {
+  __$$JsonRpcCallResult_ErrCopyWithImpl(_$JsonRpcCallResult_Err _value,
+      $Res Function(_$JsonRpcCallResult_Err) _then)
+      : super(_value, _then);
+
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({
+    Object? field0 = null,
+  }) {
+    return _then(_$JsonRpcCallResult_Err(
+      null == field0
+          ? _value.field0
+          : field0 // ignore: cast_nullable_to_non_nullable
+              as SmoldotFlutterError,
+    ));
+  }
+}
+
+/// @nodoc
+
+class _$JsonRpcCallResult_Err implements JsonRpcCallResult_Err {
+  const _$JsonRpcCallResult_Err(this.field0);
+
+  @override
+  final SmoldotFlutterError field0;
+
+  @override
+  String toString() {
+    return 'JsonRpcCallResult.err(field0: $field0)';
+  }
+
+  @override
+  bool operator ==(dynamic other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$JsonRpcCallResult_Err &&
+            (identical(other.field0, field0) || other.field0 == field0));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, field0);
+
+  @JsonKey(ignore: true)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$JsonRpcCallResult_ErrCopyWith<_$JsonRpcCallResult_Err> get copyWith =>
+      __$$JsonRpcCallResult_ErrCopyWithImpl<_$JsonRpcCallResult_Err>(
+          this, _$identity);
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(String field0) ok,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) {
+    return err(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(String field0)? ok,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) {
+    return err?.call(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(String field0)? ok,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) {
+    if (err != null) {
+      return err(field0);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(JsonRpcCallResult_Ok value) ok,
+    required TResult Function(JsonRpcCallResult_Err value) err,
+  }) {
+    return err(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(JsonRpcCallResult_Ok value)? ok,
+    TResult? Function(JsonRpcCallResult_Err value)? err,
+  }) {
+    return err?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(JsonRpcCallResult_Ok value)? ok,
+    TResult Function(JsonRpcCallResult_Err value)? err,
+    required TResult orElse(),
+  }) {
+    if (err != null) {
+      return err(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class JsonRpcCallResult_Err implements JsonRpcCallResult {
+  const factory JsonRpcCallResult_Err(final SmoldotFlutterError field0) =
+      _$JsonRpcCallResult_Err;
+
+  @override
+  SmoldotFlutterError get field0;
+  @JsonKey(ignore: true)
+  _$$JsonRpcCallResult_ErrCopyWith<_$JsonRpcCallResult_Err> get copyWith =>
+      throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+mixin _$SubscriptionEvent {
+  Object get field0 => throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(String field0) notification,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(String field0)? notification,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(String field0)? notification,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(SubscriptionEvent_Notification value)
+        notification,
+    required TResult Function(SubscriptionEvent_Err value) err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(SubscriptionEvent_Notification value)? notification,
+    TResult? Function(SubscriptionEvent_Err value)? err,
+  }) =>
+      throw _privateConstructorUsedError;
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(SubscriptionEvent_Notification value)? notification,
+    TResult Function(SubscriptionEvent_Err value)? err,
+    required TResult orElse(),
+  }) =>
+      throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+abstract class $SubscriptionEventCopyWith<$Res> {
+  factory $SubscriptionEventCopyWith(
+          SubscriptionEvent value, $Res Function(SubscriptionEvent) then) =
+      _$SubscriptionEventCopyWithImpl<$Res, SubscriptionEvent>;
+}
+
+/// @nodoc
+class _$SubscriptionEventCopyWithImpl<$Res, $Val extends SubscriptionEvent>
+    implements $SubscriptionEventCopyWith<$Res> {
+  _$SubscriptionEventCopyWithImpl(this._value, this._then);
+
+  // ignore: unused_field
+  final $Val _value;
+  // ignore: unused_field
+  final $Res Function($Val) _then;
+}
+
+/// @nodoc
+abstract class _$$SubscriptionEvent_NotificationCopyWith<$Res> {
+  factory _$$SubscriptionEvent_NotificationCopyWith(
+          _$SubscriptionEvent_Notification value,
+          $Res Function(_$SubscriptionEvent_Notification) then) =
+      __$$SubscriptionEvent_NotificationCopyWithImpl<$Res>;
+  @useResult
+  $Res call({String field0});
+}
+
+/// @nodoc
+class __$$SubscriptionEvent_NotificationCopyWithImpl<$Res>
+    extends _$SubscriptionEventCopyWithImpl<$Res,
+        _$SubscriptionEvent_Notification>
+    implements _$$SubscriptionEvent_NotificationCopyWith<$Res> {
+  __$$SubscriptionEvent_NotificationCopyWithImpl(
+      _$SubscriptionEvent_Notification _value,
+      $Res Function(_$SubscriptionEvent_Notification) _then)
+      : super(_value, _then);
+
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({
+    Object? field0 = null,
+  }) {
+    return _then(_$SubscriptionEvent_Notification(
+      null == field0
+          ? _value.field0
+          : field0 // ignore: cast_nullable_to_non_nullable
+              as String,
+    ));
+  }
+}
+
+/// @nodoc
+
+class _$SubscriptionEvent_Notification
+    implements SubscriptionEvent_Notification {
+  const _$SubscriptionEvent_Notification(this.field0);
+
+  @override
+  final String field0;
+
+  @override
+  String toString() {
+    return 'SubscriptionEvent.notification(field0: $field0)';
+  }
+
+  @override
+  bool operator ==(dynamic other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$SubscriptionEvent_Notification &&
+            (identical(other.field0, field0) || other.field0 == field0));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, field0);
+
+  @JsonKey(ignore: true)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$SubscriptionEvent_NotificationCopyWith<_$SubscriptionEvent_Notification>
+      get copyWith => __$$SubscriptionEvent_NotificationCopyWithImpl<
+          _$SubscriptionEvent_Notification>(this, _$identity);
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(String field0) notification,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) {
+    return notification(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(String field0)? notification,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) {
+    return notification?.call(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(String field0)? notification,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) {
+    if (notification != null) {
+      return notification(field0);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(SubscriptionEvent_Notification value)
+        notification,
+    required TResult Function(SubscriptionEvent_Err value) err,
+  }) {
+    return notification(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(SubscriptionEvent_Notification value)? notification,
+    TResult? Function(SubscriptionEvent_Err value)? err,
+  }) {
+    return notification?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(SubscriptionEvent_Notification value)? notification,
+    TResult Function(SubscriptionEvent_Err value)? err,
+    required TResult orElse(),
+  }) {
+    if (notification != null) {
+      return notification(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class SubscriptionEvent_Notification implements SubscriptionEvent {
+  const factory SubscriptionEvent_Notification(final String field0) =
+      _$SubscriptionEvent_Notification;
+
+  @override
+  String get field0;
+  @JsonKey(ignore: true)
+  _$$SubscriptionEvent_NotificationCopyWith<_$SubscriptionEvent_Notification>
+      get copyWith => throw _privateConstructorUsedError;
+}
+
+/// @nodoc
+abstract class _$$SubscriptionEvent_ErrCopyWith<$Res> {
+  factory _$$SubscriptionEvent_ErrCopyWith(_$SubscriptionEvent_Err value,
+          $Res Function(_$SubscriptionEvent_Err) then) =
+      __$$SubscriptionEvent_ErrCopyWithImpl<$Res>;
+  @useResult
+  $Res call({SmoldotFlutterError field0});
+}
+
+/// @nodoc
+class __$$SubscriptionEvent_ErrCopyWithImpl<$Res>
+    extends _$SubscriptionEventCopyWithImpl<$Res, _$SubscriptionEvent_Err>
+    implements _$$SubscriptionEvent_ErrCopyWith<$Res> {
+  __$$SubscriptionEvent_ErrCopyWithImpl(_$SubscriptionEvent_Err _value,
+      $Res Function(_$SubscriptionEvent_Err) _then)
+      : super(_value, _then);
+
+  @pragma('vm:prefer-inline')
+  @override
+  $Res call({
+    Object? field0 = null,
+  }) {
+    return _then(_$SubscriptionEvent_Err(
+      null == field0
+          ? _value.field0
+          : field0 // ignore: cast_nullable_to_non_nullable
+              as SmoldotFlutterError,
+    ));
+  }
+}
+
+/// @nodoc
+
+class _$SubscriptionEvent_Err implements SubscriptionEvent_Err {
+  const _$SubscriptionEvent_Err(this.field0);
+
+  @override
+  final SmoldotFlutterError field0;
+
+  @override
+  String toString() {
+    return 'SubscriptionEvent.err(field0: $field0)';
+  }
+
+  @override
+  bool operator ==(dynamic other) {
+    return identical(this, other) ||
+        (other.runtimeType == runtimeType &&
+            other is _$SubscriptionEvent_Err &&
+            (identical(other.field0, field0) || other.field0 == field0));
+  }
+
+  @override
+  int get hashCode => Object.hash(runtimeType, field0);
+
+  @JsonKey(ignore: true)
+  @override
+  @pragma('vm:prefer-inline')
+  _$$SubscriptionEvent_ErrCopyWith<_$SubscriptionEvent_Err> get copyWith =>
+      __$$SubscriptionEvent_ErrCopyWithImpl<_$SubscriptionEvent_Err>(
+          this, _$identity);
+
+  @override
+  @optionalTypeArgs
+  TResult when<TResult extends Object?>({
+    required TResult Function(String field0) notification,
+    required TResult Function(SmoldotFlutterError field0) err,
+  }) {
+    return err(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? whenOrNull<TResult extends Object?>({
+    TResult? Function(String field0)? notification,
+    TResult? Function(SmoldotFlutterError field0)? err,
+  }) {
+    return err?.call(field0);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeWhen<TResult extends Object?>({
+    TResult Function(String field0)? notification,
+    TResult Function(SmoldotFlutterError field0)? err,
+    required TResult orElse(),
+  }) {
+    if (err != null) {
+      return err(field0);
+    }
+    return orElse();
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult map<TResult extends Object?>({
+    required TResult Function(SubscriptionEvent_Notification value)
+        notification,
+    required TResult Function(SubscriptionEvent_Err value) err,
+  }) {
+    return err(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult? mapOrNull<TResult extends Object?>({
+    TResult? Function(SubscriptionEvent_Notification value)? notification,
+    TResult? Function(SubscriptionEvent_Err value)? err,
+  }) {
+    return err?.call(this);
+  }
+
+  @override
+  @optionalTypeArgs
+  TResult maybeMap<TResult extends Object?>({
+    TResult Function(SubscriptionEvent_Notification value)? notification,
+    TResult Function(SubscriptionEvent_Err value)? err,
+    required TResult orElse(),
+  }) {
+    if (err != null) {
+      return err(this);
+    }
+    return orElse();
+  }
+}
+
+abstract class SubscriptionEvent_Err implements SubscriptionEvent {
+  const factory SubscriptionEvent_Err(final SmoldotFlutterError field0) =
+      _$SubscriptionEvent_Err;
+
+  @override
+  SmoldotFlutterError get field0;
+  @JsonKey(ignore: true)
+  _$$SubscriptionEvent_ErrCopyWith<_$SubscriptionEvent_Err> get copyWith =>
+      throw _privateConstructorUsedError;
+}
@@ -19,3 +19,16 @@ final SmoldotFlutter api = SmoldotFlutterImpl(
     io.Platform.isIOS || io.Platform.isMacOS
         ? DynamicLibrary.executable()
         : DynamicLibrary.open(_dylib));
+
+extension JsonRpcCall on SmoldotFlutter {
+  /// Sends a JSON-RPC request through `jsonRpcCall`, and completes with the `result` of its
+  /// response as JSON text, or with the error that prevented it from being received.
+  Future<String> call(
+      {required ChainHandle chain,
+      required String method,
+      String params = '[]'}) async {
+    final outcome =
+        await jsonRpcCall(chain: chain, method: method, params: params).first;
+    return outcome.when(ok: (result) => result, err: (error) => throw error);
+  }
+}
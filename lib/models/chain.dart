@@ -1,14 +1,17 @@
 import 'dart:async';
-import 'dart:convert';
-import 'package:deep_pick/deep_pick.dart';
 import 'package:flutter/material.dart';
 import 'package:flutter/services.dart' show rootBundle;
+import 'package:path_provider/path_provider.dart';
 import 'package:shared_preferences/shared_preferences.dart';
 import '../ffi.dart';
 
-class Chains extends ChangeNotifier {
+// Time given to the light client to save the database of a chain before it stops syncing it
+const _saveTimeoutMillis = 2000;
+
+class Chains extends ChangeNotifier with WidgetsBindingObserver {
   late Chain selected;
   final List<RelayChain> chains;
+  bool _suspended = false;
 
   Chains(this.chains) {
     // Initialise logging
@@ -17,12 +20,49 @@ class Chains extends ChangeNotifier {
       debugPrint(
           '${event.level} [${event.tag}]: ${event.msg}(rust_time=${event.timeMillis})');
     });
-    // Initialise light client
+    // Initialise light client, shutting down the one left behind by a hot restart if any. The
+    // databases of the chains are saved by the light client, so that they resume syncing from
+    // where they left off
     debugPrint('[Chain] api.initLightClient');
-    api.initLightClient();
-    // Start current chain sync automatically
     selected = chains.first;
-    selected.startSync();
+    api
+        .shutdownLightClient()
+        .then((_) => getApplicationSupportDirectory())
+        .then((directory) => api.initLightClient(
+            config: LightClientConfig(
+                clientName: 'trappist-extra',
+                clientVersion: '0.1.0',
+                databaseDir: '${directory.path}/databases')))
+        .then((_) {
+      // Start current chain sync automatically
+      selected.startSync();
+    });
+    WidgetsBinding.instance.addObserver(this);
+  }
+
+  @override
+  void didChangeAppLifecycleState(AppLifecycleState state) async {
+    // Limit the background work of the light client while the app isn't in the foreground
+    final throttled = state != AppLifecycleState.resumed;
+    debugPrint('[Chain] api.throttleLightClient($throttled)');
+    await api.throttleLightClient(throttled: throttled);
+    if (state == AppLifecycleState.paused && !_suspended) {
+      // Save the database of the current chain before the app gets suspended, so that it resumes
+      // syncing from where it left off
+      _suspended = true;
+      debugPrint('[Chain] api.suspendAllChains');
+      await api.suspendAllChains(saveTimeoutMillis: _saveTimeoutMillis);
+      await selected.stopSync(suspended: true);
+    } else if (state == AppLifecycleState.resumed && _suspended) {
+      _suspended = false;
+      await selected.startSync();
+    }
+  }
+
+  @override
+  void dispose() {
+    WidgetsBinding.instance.removeObserver(this);
+    super.dispose();
   }
 
   // Selects the supplied chain.
@@ -42,34 +82,27 @@ class Chains extends ChangeNotifier {
 abstract class Chain extends ChangeNotifier {
   String _chainSpec;
   int? _currentBlock;
-  Timer? _health;
+  ChainHandle? _handle;
   bool _initialised = false;
-  int _lastDbSnapshot = 0;
   int _peers = 0;
-  Stream<String>? _stream;
-  StreamSubscription<String>? _streamSubscription;
+  StreamSubscription<ChainStatusEvent>? _statusSubscription;
 
   final String name;
   final Widget logo;
 
-  static final Future<SharedPreferences> _sharedPreferences =
-      SharedPreferences.getInstance();
-
   Chain(this.name, this._chainSpec, this.logo);
 
   set currentBlock(int? currentBlock) {
-    _currentBlock = currentBlock;
-    notifyListeners();
+    if (currentBlock != _currentBlock) {
+      _currentBlock = currentBlock;
+      notifyListeners();
+    }
   }
 
   int? get currentBlock {
     return _currentBlock;
   }
 
-  int get lastDbSnapshot {
-    return _lastDbSnapshot;
-  }
-
   set peers(int? peers) {
     if (peers != null && peers != _peers) {
       _peers = peers;
@@ -81,123 +114,103 @@ abstract class Chain extends ChangeNotifier {
     return _peers;
   }
 
-  Stream<String>? get stream {
-    return _stream;
-  }
-
   startSync() async {
     debugPrint('[Chain] start: $name');
 
+    await _loadChainSpec();
+
+    // Start chain sync
+    debugPrint('[Chain] api.startChainSync: $name');
+    _handle = await _startChainSync();
+
+    // Follow sync status
+    _statusSubscription = _watchStatus(_handle!);
+  }
+
+  // Follows the sync status of the chain through the given handle.
+  StreamSubscription<ChainStatusEvent> _watchStatus(ChainHandle handle) {
+    debugPrint('[Chain] api.watchChainStatus: $name');
+    return api.watchChainStatus(chain: handle).listen((event) {
+      event.when(
+        status: (status) {
+          currentBlock = status.bestBlockNumber;
+          peers = status.peers;
+        },
+        err: (error) =>
+            debugPrint('[Chain] Failed to watch $name: ${error.message}'),
+      );
+    });
+  }
+
+  // Forgets the sync status of the chain once it has stopped syncing.
+  void _resetStatus() {
+    _statusSubscription?.cancel();
+    _statusSubscription = null;
+    currentBlock = null;
+    _peers = 0;
+  }
+
+  // Replaces the asset path of the chain spec by its content the first time it is needed.
+  Future<String> _loadChainSpec() async {
     if (!_initialised) {
-      // Initialise chain spec
       debugPrint('[Chain] loading chain spec: $name');
       _chainSpec = await rootBundle.loadString(_chainSpec);
       _initialised = true;
     }
+    return _chainSpec;
+  }
 
-    // Start chain sync
-    debugPrint('[Chain] api.startChainSync: $name');
-    var database = (await _sharedPreferences).getString(name);
-    await _startChainSync(database);
-
-    // Subscribe to best header
-    debugPrint('[Chain] api.sendJsonRpcRequest: $name');
-    await api.sendJsonRpcRequest(
-        chainName: name,
-        req:
-            "{\"id\":1,\"jsonrpc\":\"2.0\",\"method\":\"chain_subscribeNewHeads\",\"params\":[]}");
-
-    debugPrint('[Chain] api.listenJsonRpcResponses: $name');
-    _stream = api.listenJsonRpcResponses(chainName: name).asBroadcastStream();
-
-    // Subscribe to broadcast stream internally to track state updates
-    _streamSubscription = _stream!.listen((response) async {
-      _processResponse(response);
-    });
-
-    // Start periodic health check
-    _health = Timer.periodic(const Duration(seconds: 3), (timer) async {
-      await _checkHealth();
-    });
+  // Starts the chain from the database saved by the light client, if any.
+  Future<ChainHandle> _startChainSync() async {
+    return _migrateLegacyDatabase(
+        name,
+        (database) => api.startChainSync(
+            chainName: name,
+            chainSpec: _chainSpec,
+            database: database,
+            relayChains: []));
   }
 
-  _startChainSync(String? database) async {
-    await api.startChainSync(
-        chainName: name, chainSpec: _chainSpec, database: database ?? "");
-  }
-
-  _checkHealth() async {
-    await api.sendJsonRpcRequest(
-        chainName: name,
-        req:
-            "{\"id\":1,\"jsonrpc\":\"2.0\",\"method\":\"system_health\",\"params\":[]}");
-  }
-
-  _processResponse(String response) async {
-    final decodedData = jsonDecode(response);
-    final id = pick(decodedData, 'id').asIntOrNull();
-    final method = pick(decodedData, 'method').asStringOrNull();
-    switch (method) {
-      case "chain_newHead":
-        final int? block =
-            pick(decodedData, 'params', 'result', 'number').asIntOrNull();
-        if (block != null) {
-          if ((block - _lastDbSnapshot) >= 10) {
-            await api.sendJsonRpcRequest(
-                chainName: name,
-                req:
-                    "{\"id\":2,\"jsonrpc\":\"2.0\",\"method\":\"chainHead_unstable_finalizedDatabase\",\"params\":[]}");
-            _lastDbSnapshot = block;
-            currentBlock = block;
-          } else {
-            currentBlock = block;
-          }
-
-          // Manually set first peer (as block received) until periodic health check
-          if (peers == 0) {
-            peers = 1;
-          }
-        }
-        break;
-      default:
-        switch (id) {
-          case 1:
-            {
-              peers = pick(decodedData, 'result', 'peers').asIntOrNull();
-              break;
-            }
-          case 2:
-            {
-              // Handle chainHead_unstable_finalizedDatabase result
-              final String? database =
-                  pick(decodedData, 'result').asStringOrNull();
-              if (database != null) {
-                debugPrint('[Chain] Saving $name database');
-                (await _sharedPreferences).setString(name, database);
-              }
-            }
-        }
+  // Starts a chain from the database saved to the shared preferences by earlier versions of the
+  // app, if any, which is only used once before the light client saves its own. The database is
+  // removed once the chain has started, so that it is kept if the chain fails to start.
+  static Future<ChainHandle> _migrateLegacyDatabase(
+      String name, Future<ChainHandle> Function(String database) start) async {
+    final preferences = await SharedPreferences.getInstance();
+    final database = preferences.getString(name);
+    if (database == null) {
+      return start("");
     }
+    debugPrint('[Chain] Migrating $name database');
+    final handle = await start(database);
+    await preferences.remove(name);
+    return handle;
   }
 
-  stopSync() async {
-    debugPrint('[Chain] api.stopChainSync: $name');
-    await api.stopChainSync(chainName: name);
-    _streamSubscription?.cancel();
-    _streamSubscription = null;
-    _lastDbSnapshot = 0;
-    currentBlock = null;
-    _health?.cancel();
-    _health = null;
-    _peers = 0;
+  // Stops syncing the chain, saving its database first. A chain that has been suspended along
+  // with the others by the light client only has its resources released. Does nothing if the
+  // chain hasn't been started yet, for example if the app is paused while it starts.
+  stopSync({bool suspended = false}) async {
+    final handle = _handle;
+    if (handle == null) {
+      return;
+    }
+    _handle = null;
+    if (!suspended) {
+      debugPrint('[Chain] api.stopChainSync: $name');
+      await api.stopChainSync(
+          chain: handle, saveTimeoutMillis: _saveTimeoutMillis);
+    }
+    handle.dispose();
+    _resetStatus();
   }
 
   @override
   void dispose() {
-    if (_streamSubscription != null) {
-      debugPrint('[Chain] dispose: $_streamSubscription');
-      _streamSubscription?.cancel();
-      _streamSubscription = null;
+    if (_statusSubscription != null) {
+      debugPrint('[Chain] dispose: $_statusSubscription');
+      _statusSubscription?.cancel();
+      _statusSubscription = null;
     }
     super.dispose();
   }
@@ -219,6 +232,7 @@ class RelayChain extends Chain {
 
 class Parachain extends Chain {
   final RelayChain relayChain;
+  ChainHandle? _relayChainHandle;
 
   Parachain(String name, String chainSpec, Widget logo, this.relayChain)
       : super(name, chainSpec, logo) {
@@ -226,21 +240,48 @@ class Parachain extends Chain {
   }
 
   @override
-  _startChainSync(String? database) async {
-    // Ensure relay chain sync started
-    await relayChain.startSync();
+  Future<ChainHandle> _startChainSync() async {
+    // Start relay chain sync as a dependency of the parachain, with just enough JSON-RPC to follow
+    // its sync status
+    debugPrint('[Chain] api.startChainSync: ${relayChain.name} (relay only)');
+    final relayChainSpec = await relayChain._loadChainSpec();
+    _relayChainHandle = await Chain._migrateLegacyDatabase(
+        relayChain.name,
+        (database) => api.startChainSync(
+            chainName: relayChain.name,
+            chainSpec: relayChainSpec,
+            database: database,
+            relayChains: [],
+            options: const ChainOptions(
+                maxPendingRequests: 4, maxSubscriptions: 2)));
+    relayChain._statusSubscription =
+        relayChain._watchStatus(_relayChainHandle!);
     // Start parachain sync
-    await api.startChainSync(
-        chainName: name,
-        chainSpec: _chainSpec,
-        database: database ?? "",
-        relayChain: relayChain.name);
+    return Chain._migrateLegacyDatabase(
+        name,
+        (database) => api.startChainSync(
+            chainName: name,
+            chainSpec: _chainSpec,
+            database: database,
+            relayChains: [relayChain.name]));
   }
 
   @override
-  stopSync() async {
-    // Stop relay/parachain sync
-    await super.stopSync();
-    await relayChain.stopSync();
+  stopSync({bool suspended = false}) async {
+    // Stop parachain sync, then relay chain sync
+    await super.stopSync(suspended: suspended);
+    final relayChainHandle = _relayChainHandle;
+    if (relayChainHandle == null) {
+      return;
+    }
+    _relayChainHandle = null;
+    if (!suspended) {
+      debugPrint(
+          '[Chain] api.stopChainSync: ${relayChain.name} (relay only)');
+      await api.stopChainSync(
+          chain: relayChainHandle, saveTimeoutMillis: _saveTimeoutMillis);
+    }
+    relayChainHandle.dispose();
+    relayChain._resetStatus();
   }
 }
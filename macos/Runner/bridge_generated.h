@@ -9,11 +9,41 @@ typedef int64_t DartPort;
 
 typedef bool (*DartPostCObjectFnType)(DartPort port_id, void *message);
 
+typedef struct wire_ChainHandle {
+  const void *ptr;
+} wire_ChainHandle;
+
+typedef struct wire_LightClient {
+  const void *ptr;
+} wire_LightClient;
+
 typedef struct wire_uint_8_list {
   uint8_t *ptr;
   int32_t len;
 } wire_uint_8_list;
 
+typedef struct wire_LightClientConfig {
+  struct wire_uint_8_list *client_name;
+  struct wire_uint_8_list *client_version;
+  bool *allow_tcp;
+  bool *allow_websocket;
+  struct wire_uint_8_list *database_dir;
+  uint32_t *database_snapshot_interval_secs;
+} wire_LightClientConfig;
+
+typedef struct wire_StringList {
+  struct wire_uint_8_list **ptr;
+  int32_t len;
+} wire_StringList;
+
+typedef struct wire_ChainOptions {
+  uint32_t *max_pending_requests;
+  uint32_t *max_subscriptions;
+  bool *disable_json_rpc;
+  uint32_t *listener_buffer_size;
+  int32_t *overflow_policy;
+} wire_ChainOptions;
+
 typedef struct DartCObject *WireSyncReturn;
 
 void store_dart_post_cobject(DartPostCObjectFnType ptr);
@@ -28,35 +58,174 @@ intptr_t init_frb_dart_api_dl(void *obj);
 
 void wire_init_logger(int64_t port_);
 
-void wire_init_light_client(int64_t port_);
+void wire_init_light_client(int64_t port_, struct wire_LightClientConfig *config);
+
+void wire_create_light_client(int64_t port_, struct wire_LightClientConfig *config);
+
+void wire_shutdown_light_client(int64_t port_);
+
+void wire_light_client_shutdown(int64_t port_, struct wire_LightClient client);
+
+void wire_throttle_light_client(int64_t port_, bool throttled);
+
+void wire_light_client_throttle(int64_t port_, struct wire_LightClient client, bool throttled);
 
 void wire_start_chain_sync(int64_t port_,
                            struct wire_uint_8_list *chain_name,
                            struct wire_uint_8_list *chain_spec,
                            struct wire_uint_8_list *database,
-                           struct wire_uint_8_list *relay_chain);
-
-void wire_stop_chain_sync(int64_t port_, struct wire_uint_8_list *chain_name);
+                           struct wire_StringList *relay_chains,
+                           struct wire_ChainOptions *options);
+
+void wire_light_client_start_chain_sync(int64_t port_,
+                                        struct wire_LightClient client,
+                                        struct wire_uint_8_list *chain_name,
+                                        struct wire_uint_8_list *chain_spec,
+                                        struct wire_uint_8_list *database,
+                                        struct wire_StringList *relay_chains,
+                                        struct wire_ChainOptions *options);
+
+void wire_restart_chain_sync(int64_t port_,
+                             struct wire_ChainHandle chain,
+                             struct wire_uint_8_list *chain_spec,
+                             struct wire_uint_8_list *database,
+                             struct wire_StringList *relay_chains,
+                             struct wire_ChainOptions *options);
+
+void wire_export_chain_database(int64_t port_,
+                                struct wire_ChainHandle chain,
+                                uint64_t *max_size_bytes);
+
+void wire_import_chain_database(int64_t port_,
+                                struct wire_ChainHandle chain,
+                                struct wire_uint_8_list *database);
+
+void wire_stop_chain_sync(int64_t port_,
+                          struct wire_ChainHandle chain,
+                          uint32_t *save_timeout_millis);
+
+void wire_suspend_all_chains(int64_t port_, uint32_t save_timeout_millis);
+
+void wire_light_client_suspend_all_chains(int64_t port_,
+                                          struct wire_LightClient client,
+                                          uint32_t save_timeout_millis);
 
 void wire_send_json_rpc_request(int64_t port_,
-                                struct wire_uint_8_list *chain_name,
+                                struct wire_ChainHandle chain,
                                 struct wire_uint_8_list *req);
 
-void wire_listen_json_rpc_responses(int64_t port_, struct wire_uint_8_list *chain_name);
+void wire_json_rpc_call(int64_t port_,
+                        struct wire_ChainHandle chain,
+                        struct wire_uint_8_list *method,
+                        struct wire_uint_8_list *params);
+
+void wire_subscribe(int64_t port_,
+                    struct wire_ChainHandle chain,
+                    struct wire_uint_8_list *method,
+                    struct wire_uint_8_list *params,
+                    struct wire_uint_8_list *unsubscribe_method);
+
+void wire_listen_json_rpc_responses(int64_t port_,
+                                    struct wire_ChainHandle chain,
+                                    uint32_t *replay);
+
+void wire_ack_json_rpc_responses(int64_t port_, struct wire_ChainHandle chain, uint32_t count);
+
+void wire_unlisten_json_rpc_responses(int64_t port_, struct wire_ChainHandle chain);
+
+void wire_json_rpc_metrics(int64_t port_, struct wire_ChainHandle chain);
+
+void wire_watch_chain_status(int64_t port_, struct wire_ChainHandle chain);
+
+void wire_start_json_rpc_server(int64_t port_,
+                                uint16_t *port,
+                                struct wire_StringList *allowed_origins);
+
+void wire_light_client_start_json_rpc_server(int64_t port_,
+                                             struct wire_LightClient client,
+                                             uint16_t *port,
+                                             struct wire_StringList *allowed_origins);
+
+void wire_stop_json_rpc_server(int64_t port_);
+
+void wire_light_client_stop_json_rpc_server(int64_t port_, struct wire_LightClient client);
+
+struct wire_ChainHandle new_ChainHandle(void);
+
+struct wire_LightClient new_LightClient(void);
+
+struct wire_StringList *new_StringList_0(int32_t len);
+
+bool *new_box_autoadd_bool_0(bool value);
+
+struct wire_ChainOptions *new_box_autoadd_chain_options_0(void);
+
+struct wire_LightClientConfig *new_box_autoadd_light_client_config_0(void);
+
+int32_t *new_box_autoadd_overflow_policy_0(int32_t value);
+
+uint16_t *new_box_autoadd_u16_0(uint16_t value);
+
+uint32_t *new_box_autoadd_u32_0(uint32_t value);
+
+uint64_t *new_box_autoadd_u64_0(uint64_t value);
 
 struct wire_uint_8_list *new_uint_8_list_0(int32_t len);
 
+void drop_opaque_ChainHandle(const void *ptr);
+
+const void *share_opaque_ChainHandle(const void *ptr);
+
+void drop_opaque_LightClient(const void *ptr);
+
+const void *share_opaque_LightClient(const void *ptr);
+
 void free_WireSyncReturn(WireSyncReturn ptr);
 
 static int64_t dummy_method_to_enforce_bundling(void) {
     int64_t dummy_var = 0;
     dummy_var ^= ((int64_t) (void*) wire_init_logger);
     dummy_var ^= ((int64_t) (void*) wire_init_light_client);
+    dummy_var ^= ((int64_t) (void*) wire_create_light_client);
+    dummy_var ^= ((int64_t) (void*) wire_shutdown_light_client);
+    dummy_var ^= ((int64_t) (void*) wire_light_client_shutdown);
+    dummy_var ^= ((int64_t) (void*) wire_throttle_light_client);
+    dummy_var ^= ((int64_t) (void*) wire_light_client_throttle);
     dummy_var ^= ((int64_t) (void*) wire_start_chain_sync);
+    dummy_var ^= ((int64_t) (void*) wire_light_client_start_chain_sync);
+    dummy_var ^= ((int64_t) (void*) wire_restart_chain_sync);
+    dummy_var ^= ((int64_t) (void*) wire_export_chain_database);
+    dummy_var ^= ((int64_t) (void*) wire_import_chain_database);
     dummy_var ^= ((int64_t) (void*) wire_stop_chain_sync);
+    dummy_var ^= ((int64_t) (void*) wire_suspend_all_chains);
+    dummy_var ^= ((int64_t) (void*) wire_light_client_suspend_all_chains);
     dummy_var ^= ((int64_t) (void*) wire_send_json_rpc_request);
+    dummy_var ^= ((int64_t) (void*) wire_json_rpc_call);
+    dummy_var ^= ((int64_t) (void*) wire_subscribe);
     dummy_var ^= ((int64_t) (void*) wire_listen_json_rpc_responses);
+    dummy_var ^= ((int64_t) (void*) wire_ack_json_rpc_responses);
+    dummy_var ^= ((int64_t) (void*) wire_unlisten_json_rpc_responses);
+    dummy_var ^= ((int64_t) (void*) wire_json_rpc_metrics);
+    dummy_var ^= ((int64_t) (void*) wire_watch_chain_status);
+    dummy_var ^= ((int64_t) (void*) wire_start_json_rpc_server);
+    dummy_var ^= ((int64_t) (void*) wire_light_client_start_json_rpc_server);
+    dummy_var ^= ((int64_t) (void*) wire_stop_json_rpc_server);
+    dummy_var ^= ((int64_t) (void*) wire_light_client_stop_json_rpc_server);
+    dummy_var ^= ((int64_t) (void*) new_ChainHandle);
+    dummy_var ^= ((int64_t) (void*) new_LightClient);
+    dummy_var ^= ((int64_t) (void*) new_StringList_0);
+    dummy_var ^= ((int64_t) (void*) new_box_autoadd_bool_0);
+    dummy_var ^= ((int64_t) (void*) new_box_autoadd_chain_options_0);
+    dummy_var ^= ((int64_t) (void*) new_box_autoadd_light_client_config_0);
+    dummy_var ^= ((int64_t) (void*) new_box_autoadd_overflow_policy_0);
+    dummy_var ^= ((int64_t) (void*) new_box_autoadd_u16_0);
+    dummy_var ^= ((int64_t) (void*) new_box_autoadd_u32_0);
+    dummy_var ^= ((int64_t) (void*) new_box_autoadd_u64_0);
     dummy_var ^= ((int64_t) (void*) new_uint_8_list_0);
+    dummy_var ^= ((int64_t) (void*) drop_opaque_ChainHandle);
+    dummy_var ^= ((int64_t) (void*) share_opaque_ChainHandle);
+    dummy_var ^= ((int64_t) (void*) drop_opaque_LightClient);
+    dummy_var ^= ((int64_t) (void*) share_opaque_LightClient);
     dummy_var ^= ((int64_t) (void*) free_WireSyncReturn);
     dummy_var ^= ((int64_t) (void*) store_dart_post_cobject);
     dummy_var ^= ((int64_t) (void*) get_dart_object);
@@ -0,0 +1,43 @@
+use std::{env, fs, path::PathBuf};
+
+// Exposes the revision of smoldot the crate is built against as `SMOLDOT_REVISION`, so that the
+// databases saved by one build can be told apart from the ones saved by another. smoldot is pulled
+// from a git branch, whose commit is only known from `Cargo.lock`.
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let lock_file = manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.exists());
+    let revision = lock_file
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|lock| smoldot_revision(&lock))
+        .unwrap_or_else(|| "unknown".into());
+
+    if let Some(lock_file) = lock_file {
+        println!("cargo:rerun-if-changed={}", lock_file.display());
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-env=SMOLDOT_REVISION={}", revision);
+}
+
+// Returns the git commit of the `smoldot` package of the lock file, or its version if it doesn't
+// come from git.
+fn smoldot_revision(lock: &str) -> Option<String> {
+    let package = lock
+        .split("[[package]]")
+        .find(|package| package.lines().any(|line| line == r#"name = "smoldot""#))?;
+    let field = |name: &str| {
+        package.lines().find_map(|line| {
+            let value = line.strip_prefix(name)?.strip_prefix(" = ")?;
+            Some(value.trim_matches('"').to_owned())
+        })
+    };
+    match field("source") {
+        Some(source) if source.starts_with("git+") => {
+            source.rsplit_once('#').map(|(_, commit)| commit.to_owned())
+        }
+        _ => field("version"),
+    }
+}
@@ -1,21 +1,324 @@
-use anyhow::{anyhow, Context};
+use async_std::channel::{self, Sender};
 use core::num::NonZeroU32;
-use flutter_rust_bridge::StreamSink;
+use flutter_rust_bridge::{RustOpaque, StreamSink};
+use futures_util::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
 use lazy_static::lazy_static;
-use log::debug;
-use parking_lot::RwLock;
+use log::{debug, warn};
 use smoldot_light::*;
-use std::{collections::HashMap, sync::{Mutex, Arc}};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock, RwLock,
+    },
+    time::Duration,
+};
 
+use crate::broadcaster::{Broadcaster, BufferConfig, OverflowPolicy};
+use crate::database::{self, Candidate, DatabaseImport, DatabaseStore};
+use crate::error::{SmoldotFlutterError, SmoldotFlutterErrorKind};
+use crate::json_rpc::{self, Batch, Router, Subscription, SubscriptionEvent, SubscriptionSink};
 use crate::logger;
+use crate::platform::{Platform, TcpConnector};
+#[cfg(feature = "server")]
+use crate::server::{Endpoint, JsonRpcServer};
+use crate::status::{ChainStatusEvent, StatusTracker};
+
+// Maximum time `json_rpc_call` and `subscribe` wait for the response to their request.
+const JSON_RPC_CALL_TIMEOUT: Duration = Duration::from_secs(30);
+// Time after which `watch_chain_status` checks the health of a chain if no new block came in.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+type SmoldotClient = smoldot_light::Client<Platform>;
 
 // Inspired by https://github.com/paritytech/smoldot/blob/5b30f5e4c4f677f7c8ff4188c0440789ba3c1adb/bin/wasm-node/rust/src/lib.rs
 lazy_static! {
-    static ref CLIENT: Mutex<Option<smoldot_light::Client<Arc<smoldot_light::platform::default::DefaultPlatform>>>> =
-        Mutex::new(None);
-    static ref CHAINS: RwLock<HashMap<String, ChainId>> = RwLock::new(HashMap::new());
-    static ref RPC_RESPONSE_STREAMS: RwLock<HashMap<String, JsonRpcResponse>> =
-        RwLock::new(HashMap::new());
+    // Instance used by the functions that don't take a `LightClient`.
+    static ref DEFAULT_CLIENT: RustOpaque<LightClient> = RustOpaque::new(LightClient {
+        client: Mutex::new(None),
+        platform: Mutex::new(None),
+        chains: RwLock::new(HashMap::new()),
+        databases: Mutex::new(None),
+        #[cfg(feature = "server")]
+        server: Mutex::new(None),
+    });
+}
+
+/// A light client with its own platform and chains, independent from the other instances.
+///
+/// The functions that don't take a `LightClient` operate on a default instance, which has to be
+/// initialized through `init_light_client`.
+pub struct LightClient {
+    client: Mutex<Option<SmoldotClient>>,
+    // Platform of `client`, kept to throttle it.
+    platform: Mutex<Option<Platform>>,
+    chains: RwLock<HashMap<String, Chain>>,
+    // Where the databases of the chains are persisted, if anywhere.
+    databases: Mutex<Option<Arc<DatabaseStore>>>,
+    #[cfg(feature = "server")]
+    server: Mutex<Option<JsonRpcServer>>,
+}
+
+impl LightClient {
+    // Creates a client running on the given platform, for example one whose parts are replaced to
+    // route networking through the APIs of the host OS, and persisting the databases of its chains
+    // to the given store.
+    pub(crate) fn with_platform(
+        platform: Platform,
+        databases: Option<DatabaseStore>,
+    ) -> RustOpaque<Self> {
+        RustOpaque::new(LightClient {
+            client: Mutex::new(Some(smoldot_light::Client::new(platform.clone()))),
+            platform: Mutex::new(Some(platform)),
+            chains: RwLock::new(HashMap::new()),
+            databases: Mutex::new(databases.map(Arc::new)),
+            #[cfg(feature = "server")]
+            server: Mutex::new(None),
+        })
+    }
+}
+
+impl fmt::Debug for LightClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LightClient").finish_non_exhaustive()
+    }
+}
+
+// Tells apart the chains successively started under the same name, so that a handle can't end up
+// referring to a chain started after its own was stopped.
+static NEXT_CHAIN_INSTANCE: AtomicU64 = AtomicU64::new(0);
+
+/// Handle to a chain started through `start_chain_sync`, identifying it in the other functions.
+///
+/// The chain stops syncing once its handle is passed to `stop_chain_sync` or dropped, unless other
+/// handles to it were obtained by starting it again under the same name.
+#[derive(Debug)]
+pub struct ChainHandle {
+    client: RustOpaque<LightClient>,
+    name: String,
+    instance: u64,
+    // Set once the handle has been passed to `stop_chain_sync`, so that dropping it doesn't
+    // release the chain a second time.
+    stopped: AtomicBool,
+}
+
+impl ChainHandle {
+    fn new(client: &RustOpaque<LightClient>, name: String, instance: u64) -> RustOpaque<Self> {
+        RustOpaque::new(ChainHandle {
+            client: client.clone(),
+            name,
+            instance,
+            stopped: AtomicBool::new(false),
+        })
+    }
+
+    // Returns the chain the handle refers to, unless it has been stopped.
+    fn chain<'a>(
+        &self,
+        chains: &'a HashMap<String, Chain>,
+    ) -> Result<&'a Chain, SmoldotFlutterError> {
+        if self.stopped.load(Ordering::Relaxed) {
+            return Err(SmoldotFlutterError::unknown_chain(&self.name));
+        }
+        find_chain(chains, &self.name, self.instance)
+    }
+}
+
+impl Drop for ChainHandle {
+    fn drop(&mut self) {
+        if !*self.stopped.get_mut() {
+            if let Err(err) = release_chain(&self.client, &self.name, self.instance) {
+                debug!("Dropped handle to chain '{:?}': {}", self.name, err);
+            }
+        }
+    }
+}
+
+// A chain started through `start_chain_sync`.
+struct Chain {
+    id: ChainId,
+    instance: u64,
+    // Number of handles to the chain that haven't been stopped or dropped yet.
+    ref_count: usize,
+    // Tells whether a later `start_chain_sync` call with the same name refers to the same chain,
+    // and is kept for `import_chain_database` to restart the chain with.
+    config: Arc<ChainConfig>,
+    // Genesis hash of the chain, computed the first time a database is imported into it, as this
+    // involves compiling and running the runtime of its genesis block. Kept when the chain is
+    // restarted with the same specification.
+    genesis_hash: Arc<OnceLock<String>>,
+    // Whether the chain handles the JSON-RPC requests of the app.
+    json_rpc: bool,
+    // Whether the chain has a JSON-RPC service, which is also the case of the chains whose JSON-RPC
+    // is disabled for the app if their database is saved.
+    json_rpc_service: bool,
+    router: Arc<Router>,
+    broadcaster: Arc<Broadcaster>,
+    // Never sent to, but closes the channel once dropped, which stops the routing task of the
+    // chain.
+    _stop_routing: Sender<()>,
+}
+
+// The specification, relay chains and options a chain was started with.
+#[derive(PartialEq, Eq)]
+struct ChainConfig {
+    chain_spec: String,
+    relay_chains: Vec<String>,
+    json_rpc_limits: Option<JsonRpcLimits>,
+    buffer_config: BufferConfig,
+}
+
+impl ChainConfig {
+    fn new(chain_spec: String, relay_chains: Vec<String>, options: ChainOptions) -> Self {
+        ChainConfig {
+            chain_spec,
+            relay_chains,
+            json_rpc_limits: options.json_rpc_limits(),
+            buffer_config: options.buffer_config(),
+        }
+    }
+}
+
+fn find_chain<'a>(
+    chains: &'a HashMap<String, Chain>,
+    chain_name: &str,
+    instance: u64,
+) -> Result<&'a Chain, SmoldotFlutterError> {
+    chains
+        .get(chain_name)
+        .filter(|chain| chain.instance == instance)
+        .ok_or_else(|| SmoldotFlutterError::unknown_chain(chain_name))
+}
+
+fn find_chain_mut<'a>(
+    chains: &'a mut HashMap<String, Chain>,
+    chain_name: &str,
+    instance: u64,
+) -> Result<&'a mut Chain, SmoldotFlutterError> {
+    chains
+        .get_mut(chain_name)
+        .filter(|chain| chain.instance == instance)
+        .ok_or_else(|| SmoldotFlutterError::unknown_chain(chain_name))
+}
+
+/// Options of a chain. Fields left unset take their default value.
+#[derive(Default)]
+pub struct ChainOptions {
+    /// Maximum number of JSON-RPC requests waiting to be processed by the chain, beyond which new
+    /// ones are rejected. Defaults to 128, and is raised to 1 if 0.
+    pub max_pending_requests: Option<u32>,
+    /// Maximum number of active JSON-RPC subscriptions, beyond which new ones are rejected.
+    /// Defaults to 1024.
+    pub max_subscriptions: Option<u32>,
+    /// Whether the chain doesn't handle JSON-RPC requests at all, for example because it only
+    /// serves as the relay chain of parachains, in which case the functions sending requests to it
+    /// or listening to its responses return a `JsonRpcDisabled` error. Defaults to `false`.
+    ///
+    /// If the client was configured with a `database_dir`, the database of the chain is still
+    /// saved, which takes a JSON-RPC service: the chain then keeps one for the crate's own use,
+    /// limited to a few pending requests and no subscriptions, but with the memory and background
+    /// work any JSON-RPC service of smoldot involves.
+    pub disable_json_rpc: Option<bool>,
+    /// Maximum number of messages waiting to be forwarded to each stream returned by
+    /// `listen_json_rpc_responses`, or kept while no stream is listening. Also the number of
+    /// messages forwarded to each stream ahead of their acknowledgement through
+    /// `ack_json_rpc_responses`. Defaults to 1024, and is raised to 1 if 0.
+    pub listener_buffer_size: Option<u32>,
+    /// What happens to the messages of a listener whose buffer is full. Defaults to
+    /// `DropNotifications`.
+    pub overflow_policy: Option<OverflowPolicy>,
+}
+
+// The JSON-RPC limits of a chain whose JSON-RPC service is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct JsonRpcLimits {
+    max_pending_requests: NonZeroU32,
+    max_subscriptions: u32,
+}
+
+// Returns the limits of the JSON-RPC service of a chain whose JSON-RPC is disabled for the app, but
+// whose database is saved through `chainHead_unstable_finalizedDatabase` requests nonetheless.
+fn internal_json_rpc_limits() -> JsonRpcLimits {
+    JsonRpcLimits {
+        max_pending_requests: NonZeroU32::new(4).unwrap(),
+        max_subscriptions: 0,
+    }
+}
+
+impl ChainOptions {
+    // Returns the JSON-RPC limits of the chain, or `None` if its JSON-RPC service is disabled.
+    fn json_rpc_limits(&self) -> Option<JsonRpcLimits> {
+        if self.disable_json_rpc.unwrap_or(false) {
+            return None;
+        }
+        Some(JsonRpcLimits {
+            max_pending_requests: NonZeroU32::new(self.max_pending_requests.unwrap_or(128))
+                .unwrap_or(NonZeroU32::MIN),
+            max_subscriptions: self.max_subscriptions.unwrap_or(1024),
+        })
+    }
+
+    fn buffer_config(&self) -> BufferConfig {
+        BufferConfig {
+            capacity: self.listener_buffer_size.unwrap_or(1024).max(1) as usize,
+            policy: self
+                .overflow_policy
+                .unwrap_or(OverflowPolicy::DropNotifications),
+        }
+    }
+}
+
+/// Statistics about the delivery of the JSON-RPC responses of a chain to the streams returned by
+/// `listen_json_rpc_responses`.
+pub struct JsonRpcMetrics {
+    /// Number of messages dropped because a stream didn't keep up with the chain, counted once per
+    /// stream, or because no stream was listening for too long.
+    pub dropped_messages: u64,
+    /// Number of messages currently waiting to be forwarded, summed over every stream, or kept
+    /// while no stream is listening.
+    pub buffered_messages: u64,
+}
+
+/// Outcome of a call made through `json_rpc_call`.
+pub enum JsonRpcCallResult {
+    /// The `result` of the response, as JSON text.
+    Ok(String),
+    /// Why no `result` could be obtained.
+    Err(SmoldotFlutterError),
+}
+
+/// Configuration of a light client. Fields left unset take their default value.
+#[derive(Default)]
+pub struct LightClientConfig {
+    /// Name under which the client identifies itself to its peers. Defaults to the name of this
+    /// crate.
+    pub client_name: Option<String>,
+    /// Version under which the client identifies itself to its peers. Defaults to the version of
+    /// this crate.
+    pub client_version: Option<String>,
+    /// Whether peers can be connected to over plain TCP. Defaults to `true`.
+    pub allow_tcp: Option<bool>,
+    /// Whether peers can be connected to over non-secure WebSocket. Defaults to `true`.
+    pub allow_websocket: Option<bool>,
+    /// Directory in which the finalized database of each chain is saved periodically, and from
+    /// which it is loaded when the chain is started without a database. The databases aren't
+    /// persisted if unset.
+    ///
+    /// The chains whose JSON-RPC is disabled through `ChainOptions` are saved as well: their
+    /// database is obtained through a JSON-RPC service that only the client itself uses.
+    ///
+    /// A saved database that is corrupted, belongs to another chain or can't be decoded by the
+    /// version of smoldot the client is built with is ignored, and the chain starts from the
+    /// checkpoint of its specification instead.
+    pub database_dir: Option<String>,
+    /// Number of seconds between two saves of the database of each chain. Defaults to 60, and is
+    /// raised to 1 if 0.
+    pub database_snapshot_interval_secs: Option<u32>,
 }
 
 pub struct LogEntry {
@@ -34,37 +337,597 @@ pub fn init_logger(log_stream_sink: StreamSink<LogEntry>) -> anyhow::Result<()>
     Ok(())
 }
 
-pub fn init_light_client() -> anyhow::Result<()> {
-    let mut client_lock = CLIENT.lock().unwrap();
-    assert!(client_lock.is_none());
-
+/// Initializes the default light client, with the default configuration if `config` is `None`.
+pub fn init_light_client(config: Option<LightClientConfig>) -> Result<(), SmoldotFlutterError> {
+    let mut client_lock = DEFAULT_CLIENT.client.lock()?;
+    if client_lock.is_some() {
+        return Err(SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::AlreadyInitialized,
+            "The light client has already been initialized.",
+        ));
+    }
+    let config = config.unwrap_or_default();
+    *DEFAULT_CLIENT.databases.lock()? = database_store(&config).map(Arc::new);
+    let platform = platform(config);
+    *DEFAULT_CLIENT.platform.lock()? = Some(platform.clone());
     // Initialize the client. This does nothing except allocate resources.
-    // The `Client` struct requires a generic parameter that provides platform bindings. In this
-    // example, we provide `AsyncStdTcpWebSocket`, which are the "plug and play" default platform.
-    let client =
-        smoldot_light::Client::new(
-            smoldot_light::platform::default::DefaultPlatform::new(
-                env!("CARGO_PKG_NAME").into(),
-                env!("CARGO_PKG_VERSION").into(),
-            ),
-        );
+    *client_lock = Some(smoldot_light::Client::new(platform));
 
-    *client_lock = Some(client);
+    Ok(())
+}
+
+/// Creates a new light client, independent from the default one and from the other instances, with
+/// the default configuration if `config` is `None`.
+///
+/// The client is shut down once it has been passed to `light_client_shutdown`, or once it and the
+/// handles to its chains have all been dropped.
+pub fn create_light_client(config: Option<LightClientConfig>) -> RustOpaque<LightClient> {
+    let config = config.unwrap_or_default();
+    let databases = database_store(&config);
+    LightClient::with_platform(platform(config), databases)
+}
+
+fn database_store(config: &LightClientConfig) -> Option<DatabaseStore> {
+    let dir = config.database_dir.as_ref()?;
+    let snapshot_interval = config.database_snapshot_interval_secs.unwrap_or(60).max(1);
+    Some(DatabaseStore::new(
+        dir,
+        Duration::from_secs(snapshot_interval.into()),
+    ))
+}
+
+// The `Client` struct requires a generic parameter that provides platform bindings. By default,
+// `Platform` behaves like the "plug and play" `DefaultPlatform` of smoldot, unless the app replaces
+// some of its parts through `platform::customize`.
+fn platform(config: LightClientConfig) -> Platform {
+    Platform::builder(
+        config
+            .client_name
+            .unwrap_or_else(|| env!("CARGO_PKG_NAME").into()),
+        config
+            .client_version
+            .unwrap_or_else(|| env!("CARGO_PKG_VERSION").into()),
+    )
+    .connector(TcpConnector::new(
+        config.allow_tcp.unwrap_or(true),
+        config.allow_websocket.unwrap_or(true),
+    ))
+    .customized()
+    .build()
+}
+
+/// Stops syncing every chain and the JSON-RPC server, and drops the default client, after which
+/// `init_light_client` can be called again, for example after a hot restart of the Flutter app.
+///
+/// Does nothing if the client isn't initialized.
+pub fn shutdown_light_client() -> Result<(), SmoldotFlutterError> {
+    shutdown(&DEFAULT_CLIENT)
+}
+
+/// Stops syncing every chain and the JSON-RPC server of the given light client, and drops its
+/// client, after which it can't be used anymore.
+///
+/// Does nothing if the client has already been shut down.
+pub fn light_client_shutdown(client: RustOpaque<LightClient>) -> Result<(), SmoldotFlutterError> {
+    shutdown(&client)
+}
+
+fn shutdown(light_client: &LightClient) -> Result<(), SmoldotFlutterError> {
+    stop_server(light_client)?;
+    let mut chains_guard = light_client.chains.write()?;
+    let mut client_lock = light_client.client.lock()?;
+    let mut client = match client_lock.take() {
+        Some(client) => client,
+        None => return Ok(()),
+    };
+
+    for (chain_name, chain) in chains_guard.drain() {
+        remove_chain(&mut client, &chain_name, chain);
+    }
 
+    // Dropping the client stops all its background tasks
+    drop(client);
+    light_client.platform.lock()?.take();
+    debug!("Light client shut down.");
     Ok(())
 }
 
+/// Sets whether the background work of the default light client is limited, for example while the
+/// app is inactive. While throttled, the timers of the client are postponed so that they fire
+/// together every few seconds rather than waking the client up one after the other, while the data
+/// received from peers is still processed as it comes in.
+///
+/// Does nothing if the client isn't initialized.
+pub fn throttle_light_client(throttled: bool) -> Result<(), SmoldotFlutterError> {
+    throttle(&DEFAULT_CLIENT, throttled)
+}
+
+/// Sets whether the background work of the given light client is limited, as
+/// `throttle_light_client` does.
+pub fn light_client_throttle(
+    client: RustOpaque<LightClient>,
+    throttled: bool,
+) -> Result<(), SmoldotFlutterError> {
+    throttle(&client, throttled)
+}
+
+fn throttle(light_client: &LightClient, throttled: bool) -> Result<(), SmoldotFlutterError> {
+    if let Some(platform) = &*light_client.platform.lock()? {
+        platform.set_throttled(throttled);
+        debug!("Light client throttled: {}", throttled);
+    }
+    Ok(())
+}
+
+/// Starts syncing a chain on the default light client, returning the handle to pass to the other
+/// functions.
+///
+/// A parachain must be given the names of the chains started earlier among which to look for its
+/// relay chain, which is the one whose id matches the `relay_chain` of its specification.
+///
+/// Starting a chain under a name that is already in use shares the existing chain if it was
+/// started with the same specification, relay chains and options, in which case it keeps syncing
+/// until every handle to it has been stopped or dropped. Otherwise, an error is returned.
+///
+/// If `database` is empty and the client was configured with a `database_dir`, the chain is
+/// started from the database last saved for it, if any.
+///
+/// The chain is started with the default options if `options` is `None`.
 pub fn start_chain_sync(
     chain_name: String,
     chain_spec: String,
     database: String,
-    relay_chain: Option<String>,
-) -> anyhow::Result<()> {
-    let mut client_lock = CLIENT.lock().unwrap();
-    assert!(client_lock.is_some());
+    relay_chains: Vec<String>,
+    options: Option<ChainOptions>,
+) -> Result<RustOpaque<ChainHandle>, SmoldotFlutterError> {
+    start(
+        &DEFAULT_CLIENT,
+        chain_name,
+        ChainConfig::new(chain_spec, relay_chains, options.unwrap_or_default()),
+        database,
+    )
+}
+
+/// Starts syncing a chain on the given light client, like `start_chain_sync` does on the default
+/// one.
+///
+/// The relay chains of a parachain are looked for among the chains of the same client only.
+pub fn light_client_start_chain_sync(
+    client: RustOpaque<LightClient>,
+    chain_name: String,
+    chain_spec: String,
+    database: String,
+    relay_chains: Vec<String>,
+    options: Option<ChainOptions>,
+) -> Result<RustOpaque<ChainHandle>, SmoldotFlutterError> {
+    start(
+        &client,
+        chain_name,
+        ChainConfig::new(chain_spec, relay_chains, options.unwrap_or_default()),
+        database,
+    )
+}
 
-    let client = client_lock.as_mut().unwrap();
+fn start(
+    light_client: &RustOpaque<LightClient>,
+    chain_name: String,
+    config: ChainConfig,
+    database: String,
+) -> Result<RustOpaque<ChainHandle>, SmoldotFlutterError> {
+    let shared = share(
+        light_client,
+        &mut *light_client.chains.write()?,
+        &chain_name,
+        &config,
+    )?;
+    if let Some(chain) = shared {
+        return Ok(chain);
+    }
 
+    // Checking the saved database takes a while, during which the client isn't locked
+    let databases = light_client.databases.lock()?.clone();
+    let genesis_hash = Arc::default();
+    let database = saved_database(
+        databases.as_deref(),
+        &chain_name,
+        &config.chain_spec,
+        &genesis_hash,
+        database,
+    );
+
+    let mut chains_guard = light_client.chains.write()?;
+    // The chain might have been started in the meantime
+    if let Some(chain) = share(light_client, &mut chains_guard, &chain_name, &config)? {
+        return Ok(chain);
+    }
+
+    let potential_relay_chains = relay_chain_ids(&chains_guard, &chain_name, &config.relay_chains)?;
+    let mut client_lock = light_client.client.lock()?;
+    let client = initialized(&mut client_lock)?;
+
+    let (chain_id, rpc_responses) = add_chain(
+        client,
+        &chain_name,
+        &config.chain_spec,
+        &database,
+        potential_relay_chains,
+        config
+            .json_rpc_limits
+            .or_else(|| databases.as_ref().map(|_| internal_json_rpc_limits())),
+    )?;
+    let instance = NEXT_CHAIN_INSTANCE.fetch_add(1, Ordering::Relaxed);
+    let json_rpc_service = rpc_responses.is_some();
+    let (router, broadcaster, stop_routing) = route_responses(
+        light_client,
+        &chain_name,
+        instance,
+        rpc_responses,
+        config.buffer_config,
+    );
+    chains_guard.insert(
+        chain_name.clone(),
+        Chain {
+            id: chain_id,
+            instance,
+            ref_count: 1,
+            json_rpc: config.json_rpc_limits.is_some(),
+            json_rpc_service,
+            config: Arc::new(config),
+            genesis_hash,
+            router,
+            broadcaster,
+            _stop_routing: stop_routing,
+        },
+    );
+    if let Some(databases) = databases {
+        async_std::task::spawn(save_databases(
+            light_client.clone(),
+            chain_name.clone(),
+            instance,
+            databases,
+        ));
+    }
+
+    Ok(ChainHandle::new(light_client, chain_name, instance))
+}
+
+// Returns a new handle to the chain if it is already started with the same configuration, or an
+// error if it is started with another one.
+fn share(
+    light_client: &RustOpaque<LightClient>,
+    chains: &mut HashMap<String, Chain>,
+    chain_name: &str,
+    config: &ChainConfig,
+) -> Result<Option<RustOpaque<ChainHandle>>, SmoldotFlutterError> {
+    let Some(chain) = chains.get_mut(chain_name) else {
+        return Ok(None);
+    };
+    if *chain.config != *config {
+        return Err(SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::DuplicateChain,
+            format!(
+                "Chain '{:?}' is already started with a different specification, relay chains or options.",
+                chain_name
+            ),
+        ));
+    }
+    chain.ref_count += 1;
+    Ok(Some(ChainHandle::new(
+        light_client,
+        chain_name.to_owned(),
+        chain.instance,
+    )))
+}
+
+/// Replaces the chain by a new one started with the given parameters, for example to pick up an
+/// updated chain specification, without affecting the handles to it.
+///
+/// The new chain is started before the old one is removed, so that the old one is left untouched
+/// if the new one can't be started. The subscriptions and response listeners of the old chain are
+/// closed, and the requests still waiting for a response from it fail with `ChainRemoved`.
+///
+/// The parachains of a restarted relay chain aren't restarted along with it: they keep syncing
+/// through the old relay chain, which keeps running in the background until they are restarted or
+/// stopped themselves.
+///
+/// If `database` is empty, the new chain is started from the database last saved for the chain,
+/// as with `start_chain_sync`. The new chain is started with the default options if `options` is
+/// `None`.
+///
+/// The light client runs a single chain per genesis block, so a new chain with the same genesis
+/// block as the old one keeps syncing from where the old one was rather than from `database`.
+/// `import_chain_database` restarts a chain from a database instead.
+pub fn restart_chain_sync(
+    chain: RustOpaque<ChainHandle>,
+    chain_spec: String,
+    database: String,
+    relay_chains: Vec<String>,
+    options: Option<ChainOptions>,
+) -> Result<(), SmoldotFlutterError> {
+    let config = ChainConfig::new(chain_spec, relay_chains, options.unwrap_or_default());
+    restart(&chain, Arc::new(config), database, Replacement::AddFirst)
+}
+
+// How `restart` replaces a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Replacement {
+    // The new chain is added before the old one is removed, so that the old one is left untouched
+    // if the new one can't be added. The light client runs a single chain per genesis block
+    // though, so the new chain keeps syncing from where the old one was if they share it, rather
+    // than from its own database.
+    AddFirst,
+    // The old chain is removed before the new one is added, which then starts from its database.
+    // The chain is stopped if the new one can't be added.
+    RemoveFirst,
+}
+
+fn restart(
+    chain: &ChainHandle,
+    config: Arc<ChainConfig>,
+    database: String,
+    replacement: Replacement,
+) -> Result<(), SmoldotFlutterError> {
+    let genesis_hash = {
+        let chains_guard = chain.client.chains.read()?;
+        let entry = chain.chain(&chains_guard)?;
+        if entry.config.chain_spec == config.chain_spec {
+            entry.genesis_hash.clone()
+        } else {
+            Arc::default()
+        }
+    };
+    let databases = chain.client.databases.lock()?.clone();
+    let database = saved_database(
+        databases.as_deref(),
+        &chain.name,
+        &config.chain_spec,
+        &genesis_hash,
+        database,
+    );
+
+    let mut chains_guard = chain.client.chains.write()?;
+    let ref_count = chain.chain(&chains_guard)?.ref_count;
+
+    let potential_relay_chains = relay_chain_ids(&chains_guard, &chain.name, &config.relay_chains)?;
+    let mut client_lock = chain.client.client.lock()?;
+    let client = initialized(&mut client_lock)?;
+
+    if replacement == Replacement::RemoveFirst {
+        let old_chain = chains_guard.remove(&chain.name).unwrap();
+        remove_chain(client, &chain.name, old_chain);
+    }
+    let (chain_id, rpc_responses) = add_chain(
+        client,
+        &chain.name,
+        &config.chain_spec,
+        &database,
+        potential_relay_chains,
+        config
+            .json_rpc_limits
+            .or_else(|| databases.as_ref().map(|_| internal_json_rpc_limits())),
+    )?;
+    let json_rpc_service = rpc_responses.is_some();
+    let (router, broadcaster, stop_routing) = route_responses(
+        &chain.client,
+        &chain.name,
+        chain.instance,
+        rpc_responses,
+        config.buffer_config,
+    );
+    let new_chain = Chain {
+        id: chain_id,
+        instance: chain.instance,
+        ref_count,
+        json_rpc: config.json_rpc_limits.is_some(),
+        json_rpc_service,
+        config,
+        genesis_hash,
+        router,
+        broadcaster,
+        _stop_routing: stop_routing,
+    };
+    if let Some(old_chain) = chains_guard.insert(chain.name.clone(), new_chain) {
+        remove_chain(client, &chain.name, old_chain);
+    }
+
+    Ok(())
+}
+
+/// Returns the database of the chain, which can be passed to `start_chain_sync` or
+/// `import_chain_database` to start syncing the chain from its current finalized block rather than
+/// from its specification, for example to ship a recent checkpoint along with the app.
+///
+/// The database is trimmed down to `max_size_bytes` if given, by leaving out the information that
+/// can be retrieved from the network again.
+pub fn export_chain_database(
+    chain: RustOpaque<ChainHandle>,
+    max_size_bytes: Option<u64>,
+) -> Result<String, SmoldotFlutterError> {
+    if chain.stopped.load(Ordering::Relaxed) {
+        return Err(SmoldotFlutterError::unknown_chain(&chain.name));
+    }
+    async_std::task::block_on(finalized_database(
+        &chain.client,
+        &chain.name,
+        chain.instance,
+        max_size_bytes,
+    ))
+}
+
+/// Restarts the chain from the given database, keeping its specification, relay chains and
+/// options, like `restart_chain_sync` does, except that the old chain is removed before the new
+/// one is started, so that the new one doesn't keep syncing from where the old one was.
+///
+/// Unlike `start_chain_sync`, which silently ignores the databases it can't use, tells whether the
+/// database was accepted, or why it was ignored. A database that belongs to another chain, or
+/// without a finalized block that can be decoded, is ignored right away and the chain is left
+/// untouched. Otherwise, whether the light client accepted the database is told by the finalized
+/// block of the restarted chain, which is restarted again from the database it had beforehand if
+/// it didn't. This requires the chain to have a JSON-RPC service, which the chains whose JSON-RPC
+/// is disabled only have if their database is saved.
+pub fn import_chain_database(
+    chain: RustOpaque<ChainHandle>,
+    database: String,
+) -> Result<DatabaseImport, SmoldotFlutterError> {
+    let (config, cached_genesis_hash) = {
+        let chains_guard = chain.client.chains.read()?;
+        let entry = chain.chain(&chains_guard)?;
+        if !entry.json_rpc_service {
+            return Err(SmoldotFlutterError::json_rpc_disabled(&chain.name));
+        }
+        (entry.config.clone(), entry.genesis_hash.clone())
+    };
+    let genesis_hash = database::genesis_hash(&config.chain_spec, &cached_genesis_hash)?;
+
+    let import = match database::check(&config.chain_spec, genesis_hash, &database)? {
+        Candidate::Ignored(import) => import,
+        Candidate::Usable(finalized_block) => {
+            let export = |chain: &ChainHandle| {
+                async_std::task::block_on(finalized_database(
+                    &chain.client,
+                    &chain.name,
+                    chain.instance,
+                    None,
+                ))
+            };
+            // The old chain has to be removed first for the light client to use the database
+            let previous = export(&chain)?;
+            restart(&chain, config.clone(), database, Replacement::RemoveFirst)?;
+            let import = finalized_block.outcome(&export(&chain)?);
+            if import != DatabaseImport::Accepted {
+                restart(&chain, config, previous, Replacement::RemoveFirst)?;
+            }
+            import
+        }
+    };
+    debug!(
+        "Database imported into chain '{:?}': {:?}",
+        chain.name, import
+    );
+    Ok(import)
+}
+
+// Returns the database of the chain, as returned by `chainHead_unstable_finalizedDatabase`.
+async fn finalized_database(
+    light_client: &LightClient,
+    chain_name: &str,
+    instance: u64,
+    max_size_bytes: Option<u64>,
+) -> Result<String, SmoldotFlutterError> {
+    let params = match max_size_bytes {
+        Some(max_size_bytes) => format!("[{}]", max_size_bytes),
+        None => "[]".into(),
+    };
+    let database = internal_request(
+        light_client,
+        chain_name,
+        instance,
+        "chainHead_unstable_finalizedDatabase",
+        &params,
+    )
+    .await?;
+    serde_json::from_str(&database).map_err(|err| {
+        SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::MalformedResponse,
+            format!("Invalid database of chain '{:?}': {}", chain_name, err),
+        )
+    })
+}
+
+// Returns the database to start the chain from, which is the one last saved for it if none is
+// supplied. Checking the saved database caches the genesis hash of the chain in `genesis_hash`.
+fn saved_database(
+    databases: Option<&DatabaseStore>,
+    chain_name: &str,
+    chain_spec: &str,
+    genesis_hash: &OnceLock<String>,
+    database: String,
+) -> String {
+    match databases {
+        Some(databases) if database.is_empty() => {
+            let saved = databases.load(chain_name, chain_spec, genesis_hash);
+            if saved.is_some() {
+                debug!("Loaded saved database of chain '{:?}'.", chain_name);
+            }
+            saved.unwrap_or(database)
+        }
+        _ => database,
+    }
+}
+
+// Saves the finalized database of the chain periodically, until it is removed.
+async fn save_databases(
+    light_client: RustOpaque<LightClient>,
+    chain_name: String,
+    instance: u64,
+    databases: Arc<DatabaseStore>,
+) {
+    let mut saved = None;
+    loop {
+        async_std::task::sleep(databases.snapshot_interval()).await;
+        let database = match finalized_database(&light_client, &chain_name, instance, None).await {
+            Ok(database) => database,
+            Err(err) => match err.kind {
+                SmoldotFlutterErrorKind::UnknownChain
+                | SmoldotFlutterErrorKind::NotInitialized
+                | SmoldotFlutterErrorKind::Poisoned => break,
+                _ => {
+                    debug!(
+                        "Failed to snapshot database of chain '{:?}': {}",
+                        chain_name, err
+                    );
+                    continue;
+                }
+            },
+        };
+        if saved.as_ref() == Some(&database) {
+            continue;
+        }
+        match databases.save(&chain_name, &database) {
+            Ok(()) => {
+                debug!("Saved database of chain '{:?}'.", chain_name);
+                saved = Some(database);
+            }
+            Err(err) => warn!(
+                "Failed to save database of chain '{:?}': {}",
+                chain_name, err
+            ),
+        }
+    }
+}
+
+fn relay_chain_ids(
+    chains: &HashMap<String, Chain>,
+    chain_name: &str,
+    relay_chains: &[String],
+) -> Result<Vec<ChainId>, SmoldotFlutterError> {
+    relay_chains
+        .iter()
+        .map(|relay_chain| {
+            chains
+                .get(relay_chain)
+                .map(|chain| chain.id)
+                .ok_or_else(|| {
+                    SmoldotFlutterError::new(
+                        SmoldotFlutterErrorKind::RelayChainNotFound,
+                        format!(
+                            "Unknown relay chain '{:?}' for chain '{:?}'.",
+                            relay_chain, chain_name
+                        ),
+                    )
+                })
+        })
+        .collect()
+}
+
+fn add_chain(
+    client: &mut SmoldotClient,
+    chain_name: &str,
+    chain_spec: &str,
+    database: &str,
+    potential_relay_chains: Vec<ChainId>,
+    json_rpc_limits: Option<JsonRpcLimits>,
+) -> Result<(ChainId, Option<JsonRpcResponses>), SmoldotFlutterError> {
     // Ask the client to connect to a chain.
     let smoldot_light::AddChainSuccess {
         chain_id,
@@ -74,30 +937,32 @@ pub fn start_chain_sync(
             // The most important field of the configuration is the chain specification. This is a
             // JSON document containing all the information necessary for the client to connect to said
             // chain.
-            specification: &chain_spec,
+            specification: chain_spec,
 
             // Configures some constants about the JSON-RPC endpoints.
             // It is also possible to pass `Disabled`, in which case the chain will not be able to
             // handle JSON-RPC requests. This can be used to save up some resources.
-            json_rpc: smoldot_light::AddChainConfigJsonRpc::Enabled {
-                // Maximum number of JSON-RPC in the queue of requests waiting to be processed.
-                // This parameter is necessary for situations where the JSON-RPC clients aren't
-                // trusted. If you control all the requests that are sent out and don't want them
-                // to fail, feel free to pass `u32::max_value()`.
-                max_pending_requests: NonZeroU32::new(128).unwrap(),
-                // Maximum number of active subscriptions before new ones are automatically
-                // rejected. Any JSON-RPC request that causes the server to generate notifications
-                // counts as a subscription.
-                // While a typical reasonable value would be for example 64, existing UIs tend to
-                // start a lot of subscriptions, and a value such as 1024 is recommended.
-                // Similarly, if you don't want any limit, feel free to pass `u32::max_value()`.
-                max_subscriptions: 1024,
+            json_rpc: match json_rpc_limits {
+                Some(limits) => smoldot_light::AddChainConfigJsonRpc::Enabled {
+                    // Maximum number of JSON-RPC in the queue of requests waiting to be processed.
+                    // This parameter is necessary for situations where the JSON-RPC clients aren't
+                    // trusted. If you control all the requests that are sent out and don't want
+                    // them to fail, feel free to pass `u32::max_value()`.
+                    max_pending_requests: limits.max_pending_requests,
+                    // Maximum number of active subscriptions before new ones are automatically
+                    // rejected. Any JSON-RPC request that causes the server to generate
+                    // notifications counts as a subscription.
+                    // While a typical reasonable value would be for example 64, existing UIs tend
+                    // to start a lot of subscriptions, and a value such as 1024 is recommended.
+                    // Similarly, if you don't want any limit, feel free to pass
+                    // `u32::max_value()`.
+                    max_subscriptions: limits.max_subscriptions,
+                },
+                None => smoldot_light::AddChainConfigJsonRpc::Disabled,
             },
 
             // This field is necessary only if adding a parachain.
-            potential_relay_chains: relay_chain
-                .and_then(|rc| CHAINS.read().get(&rc).cloned())
-                .into_iter(),
+            potential_relay_chains: potential_relay_chains.into_iter(),
 
             // After a chain has been added, it is possible to extract a "database" (in the form of a
             // simple string). This database can later be passed back the next time the same chain is
@@ -105,7 +970,7 @@ pub fn start_chain_sync(
             // A database with an invalid format is simply ignored by the client.
             // In this example, we don't use this feature, and as such we simply pass an empty string,
             // which is intentionally an invalid database content.
-            database_content: &database,
+            database_content: database,
 
             // The client gives the possibility to insert an opaque "user data" alongside each chain.
             // This avoids having to create a separate `HashMap<ChainId, ...>` in parallel of the
@@ -113,212 +978,1528 @@ pub fn start_chain_sync(
             // In this example, this feature isn't used. The chain simply has `()`.
             user_data: (),
         })
-        .map_err(anyhow::Error::msg)
-        .with_context(|| format!("Failed to start syncing chain '{:?}'.", chain_name))?;
+        .map_err(|err| {
+            SmoldotFlutterError::from(err).context(format_args!(
+                "Failed to start syncing chain '{:?}'.",
+                chain_name
+            ))
+        })?;
 
     // The chain is now properly initialized.
 
-    // `json_rpc_responses` can only be `None` if we had passed `disable_json_rpc: true` in the
-    // configuration.
-    let rpc_responses = json_rpc_responses.unwrap();
+    // `json_rpc_responses` is `None` if and only if the JSON-RPC service was disabled.
+    Ok((chain_id, json_rpc_responses))
+}
 
-    let mut chains_guard = CHAINS.write();
-    chains_guard.insert(chain_name.clone(), chain_id);
+// Returns the router and broadcaster of the chain, along with the sender whose channel stops the
+// routing task once closed.
+fn route_responses(
+    light_client: &RustOpaque<LightClient>,
+    chain_name: &str,
+    instance: u64,
+    rpc_responses: Option<JsonRpcResponses>,
+    buffer_config: BufferConfig,
+) -> (Arc<Router>, Arc<Broadcaster>, Sender<()>) {
+    // Responses are pulled continuously so that answers to `json_rpc_call` requests reach their
+    // caller and notifications reach their subscription, while everything else is broadcast to
+    // the listeners attached through `listen_json_rpc_responses`.
+    let router = Arc::new(Router::new());
+    let broadcaster = Arc::new(Broadcaster::new(buffer_config));
+    let (stop_routing, routing_stopped) = channel::bounded(1);
+    let Some(rpc_responses) = rpc_responses else {
+        return (router, broadcaster, stop_routing);
+    };
+    let light_client = light_client.clone();
+    let router_chain_name = chain_name.to_owned();
+    let routed = router.clone();
+    async_std::task::spawn(json_rpc::route_responses(
+        chain_name.to_owned(),
+        rpc_responses,
+        router.clone(),
+        broadcaster.clone(),
+        routing_stopped,
+        move |req| {
+            send_request(
+                &light_client,
+                &router_chain_name,
+                instance,
+                Some(&routed),
+                req,
+            )
+        },
+    ));
+    (router, broadcaster, stop_routing)
+}
 
-    let mut rpc_response_streams_guard = RPC_RESPONSE_STREAMS.write();
-    rpc_response_streams_guard.insert(
-        chain_name,
-        JsonRpcResponse::Disconnected(Some(rpc_responses)),
-    );
+/// Stops syncing a chain, unless other handles to it haven't been stopped or dropped yet.
+///
+/// If `save_timeout_millis` is set and the client was configured with a `database_dir`, the
+/// finalized database of the chain is saved first, so that the chain resumes syncing from it the
+/// next time it is started. The chain is stopped regardless once the database has been saved or
+/// the timeout has elapsed.
+///
+/// The handle can't be used anymore afterwards.
+pub fn stop_chain_sync(
+    chain: RustOpaque<ChainHandle>,
+    save_timeout_millis: Option<u32>,
+) -> Result<(), SmoldotFlutterError> {
+    if chain.stopped.swap(true, Ordering::Relaxed) {
+        return Err(SmoldotFlutterError::unknown_chain(&chain.name));
+    }
+    // The chain is released even if the database couldn't be saved, as the handle has already
+    // been marked as stopped
+    let saved = match save_timeout_millis {
+        Some(save_timeout_millis) => save_databases_now(
+            &chain.client,
+            &[(chain.name.clone(), chain.instance)],
+            Duration::from_millis(u64::from(save_timeout_millis)),
+        ),
+        None => Ok(()),
+    };
+    release_chain(&chain.client, &chain.name, chain.instance)?;
+    saved
+}
+
+/// Saves the finalized database of every chain of the default light client, then stops syncing
+/// them all, for example before the app gets suspended. The chains are stopped regardless once
+/// their databases have been saved or `save_timeout_millis` has elapsed.
+///
+/// The handles to the chains can't be used anymore afterwards, and the chains have to be started
+/// again, from their saved databases, to resume syncing. The databases are only saved if the
+/// client was configured with a `database_dir`.
+///
+/// Does nothing if the client isn't initialized.
+pub fn suspend_all_chains(save_timeout_millis: u32) -> Result<(), SmoldotFlutterError> {
+    suspend(&DEFAULT_CLIENT, save_timeout_millis)
+}
+
+/// Saves the finalized database of every chain of the given light client, then stops syncing them
+/// all, as `suspend_all_chains` does.
+pub fn light_client_suspend_all_chains(
+    client: RustOpaque<LightClient>,
+    save_timeout_millis: u32,
+) -> Result<(), SmoldotFlutterError> {
+    suspend(&client, save_timeout_millis)
+}
+
+fn suspend(
+    light_client: &LightClient,
+    save_timeout_millis: u32,
+) -> Result<(), SmoldotFlutterError> {
+    let chains: Vec<_> = light_client
+        .chains
+        .read()?
+        .iter()
+        .map(|(chain_name, chain)| (chain_name.clone(), chain.instance))
+        .collect();
+    save_databases_now(
+        light_client,
+        &chains,
+        Duration::from_millis(u64::from(save_timeout_millis)),
+    )?;
+
+    let mut chains_guard = light_client.chains.write()?;
+    let mut client_lock = light_client.client.lock()?;
+    let Some(client) = client_lock.as_mut() else {
+        return Ok(());
+    };
+    for (chain_name, chain) in chains_guard.drain() {
+        remove_chain(client, &chain_name, chain);
+    }
+    debug!("Light client suspended.");
+    Ok(())
+}
+
+// Saves the finalized databases of the given chains concurrently, if the client persists them,
+// giving up on the ones that aren't saved once `timeout` has elapsed. Failures are only logged,
+// as the chains are about to be removed anyway.
+fn save_databases_now(
+    light_client: &LightClient,
+    chains: &[(String, u64)],
+    timeout: Duration,
+) -> Result<(), SmoldotFlutterError> {
+    let Some(databases) = light_client.databases.lock()?.clone() else {
+        return Ok(());
+    };
+    let saves = chains.iter().map(|(chain_name, instance)| {
+        let databases = &databases;
+        async move {
+            let saved = match finalized_database(light_client, chain_name, *instance, None).await {
+                Ok(database) => databases
+                    .save(chain_name, &database)
+                    .map_err(|err| err.to_string()),
+                Err(err) => Err(err.to_string()),
+            };
+            match saved {
+                Ok(()) => debug!("Saved database of chain '{:?}'.", chain_name),
+                Err(err) => warn!(
+                    "Failed to save database of chain '{:?}': {}",
+                    chain_name, err
+                ),
+            }
+        }
+    });
+    let saves = async_std::future::timeout(timeout, future::join_all(saves));
+    if async_std::task::block_on(saves).is_err() {
+        warn!("Timed out saving the databases of the chains.");
+    }
+    Ok(())
+}
+
+// Gives up on one of the handles to a chain, removing the chain if it was the last one.
+fn release_chain(
+    light_client: &LightClient,
+    chain_name: &str,
+    instance: u64,
+) -> Result<(), SmoldotFlutterError> {
+    let mut chains_guard = light_client.chains.write()?;
+    let chain = find_chain_mut(&mut chains_guard, chain_name, instance)?;
+    if chain.ref_count > 1 {
+        chain.ref_count -= 1;
+        return Ok(());
+    }
+
+    let mut client_lock = light_client.client.lock()?;
+    let client = initialized(&mut client_lock)?;
+
+    if let Some(chain) = chains_guard.remove(chain_name) {
+        remove_chain(client, chain_name, chain);
+    }
+    Ok(())
+}
+
+// Removes a chain that has already been taken out of the chains of its client, along with its
+// routing state.
+fn remove_chain(client: &mut SmoldotClient, chain_name: &str, chain: Chain) {
+    // Cancel the subscriptions started through `subscribe` before the chain goes away
+    for req in chain.router.unsubscribe_all() {
+        let _ = client.json_rpc_request(req, chain.id);
+    }
 
+    // This should end the JSON-RPC response stream, and thus the routing task of the chain
+    let _: () = client.remove_chain(chain.id);
+
+    // Close the streams of the listeners right away rather than when the routing task notices
+    // that the chain is gone
+    chain.broadcaster.detach_all();
+    debug!("Chain '{:?}' removed.", chain_name);
+}
+
+/// Sends a JSON-RPC request to the chain, whose response is forwarded to the stream returned by
+/// `listen_json_rpc_responses`.
+///
+/// Ids starting with `smoldot-flutter:` are reserved for the requests issued by the crate itself,
+/// and a request using one is rejected.
+///
+/// `req` can also be a JSON-RPC batch, whose requests are sent individually and whose responses
+/// are forwarded as a single batch, in the order of the requests, once they have all been
+/// received. A request of the batch that can't be sent is answered with an error in the batch.
+pub fn send_json_rpc_request(
+    chain: RustOpaque<ChainHandle>,
+    req: String,
+) -> Result<(), SmoldotFlutterError> {
+    if chain.stopped.load(Ordering::Relaxed) {
+        return Err(SmoldotFlutterError::unknown_chain(&chain.name));
+    }
+    match json_rpc::parse_batch(&req)? {
+        Some(requests) => send_batch(&chain, requests),
+        None => {
+            json_rpc::check_request_id(&req)?;
+            if !chain.chain(&*chain.client.chains.read()?)?.json_rpc {
+                return Err(SmoldotFlutterError::json_rpc_disabled(&chain.name));
+            }
+            send_request(&chain.client, &chain.name, chain.instance, None, req)
+        }
+    }
+}
+
+fn send_batch(
+    chain: &ChainHandle,
+    requests: Vec<serde_json::Value>,
+) -> Result<(), SmoldotFlutterError> {
+    let (router, broadcaster) = {
+        let chains_guard = chain.client.chains.read()?;
+        let entry = chain.chain(&chains_guard)?;
+        if !entry.json_rpc {
+            return Err(SmoldotFlutterError::json_rpc_disabled(&chain.name));
+        }
+        (entry.router.clone(), entry.broadcaster.clone())
+    };
+
+    let mut batch = Batch::new();
+    for request in requests {
+        batch.send(&router, request, None, |request| {
+            send_request(
+                &chain.client,
+                &chain.name,
+                chain.instance,
+                Some(&router),
+                request,
+            )
+        });
+    }
+    async_std::task::spawn(async move {
+        if let Some(reply) = batch.reply().await {
+            broadcaster
+                .broadcast(serde_json::Value::Array(reply).to_string())
+                .await;
+        }
+    });
     Ok(())
 }
 
-pub fn stop_chain_sync(chain_name: String) -> anyhow::Result<()> {
-    let chains_guard = CHAINS.upgradable_read();
-    if !chains_guard.contains_key(&chain_name) {
-        return Err(anyhow!("Unknown chain '{:?}'.", chain_name));
+// Sends a request to the chain. If `router` is given, the request is only sent if the chain still
+// routes its responses through it: otherwise the chain has been replaced by `restart_chain_sync`
+// since the request was registered with `router`, and its response would never reach it.
+fn send_request(
+    light_client: &LightClient,
+    chain_name: &str,
+    instance: u64,
+    router: Option<&Arc<Router>>,
+    req: String,
+) -> Result<(), SmoldotFlutterError> {
+    let chains_guard = light_client.chains.read()?;
+    let chain = find_chain(&chains_guard, chain_name, instance)?;
+    if !chain.json_rpc_service {
+        return Err(SmoldotFlutterError::json_rpc_disabled(chain_name));
+    }
+    if router.is_some_and(|router| !Arc::ptr_eq(router, &chain.router)) {
+        return Err(SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::ChainRemoved,
+            format!("Chain '{:?}' was restarted in the meantime.", chain_name),
+        ));
     }
 
-    let mut client_lock = CLIENT.lock().unwrap();
-    assert!(client_lock.is_some());
-    let client = client_lock.as_mut().unwrap();
+    // Send a JSON-RPC request to the chain.
+    // Calling this function only queues the request. It is not processed immediately.
+    // An `Err` is returned immediately if and only if the request isn't a proper JSON-RPC request
+    // or if the channel of JSON-RPC responses is clogged.
+    let mut client_lock = light_client.client.lock()?;
+    let client = initialized(&mut client_lock)?;
 
-    // Upgrade read lock to write lock
-    let mut chains_write_guard =
-        parking_lot::lock_api::RwLockUpgradableReadGuard::<'_, _, _>::upgrade(chains_guard);
-    if let Some(chain_id) = chains_write_guard.remove(&chain_name) {
-        // This should end the JSON-RPC response stream
-        let _: () = client.remove_chain(chain_id);
+    client.json_rpc_request(req, chain.id).map_err(|err| {
+        SmoldotFlutterError::from(err).context(format_args!(
+            "Failed to enqueue JSON-RPC request to chain '{:?}'.",
+            chain_name
+        ))
+    })
+}
 
-        let mut rpc_response_streams_guard = RPC_RESPONSE_STREAMS.write();
-        rpc_response_streams_guard.remove(&chain_name);
+/// Sends a JSON-RPC request to the chain, and forwards the outcome of the call to `sink` once its
+/// response has been received, before closing it.
+///
+/// The call is awaited in the background rather than on a thread of the bridge, so that slow
+/// calls don't hold up the other functions. The id of the request is allocated internally, and
+/// the response is not forwarded to the stream returned by `listen_json_rpc_responses`. `params`
+/// must be a JSON array or object.
+pub fn json_rpc_call(
+    chain: RustOpaque<ChainHandle>,
+    method: String,
+    params: String,
+    sink: StreamSink<JsonRpcCallResult>,
+) -> Result<(), SmoldotFlutterError> {
+    if chain.stopped.load(Ordering::Relaxed) {
+        return Err(SmoldotFlutterError::unknown_chain(&chain.name));
     }
+    // The handle isn't kept by the task, so that the chain can still be released in the meantime.
+    let (light_client, chain_name, instance) =
+        (chain.client.clone(), chain.name.clone(), chain.instance);
+    async_std::task::spawn(async move {
+        let outcome = request(&light_client, &chain_name, instance, &method, &params, None).await;
+        sink.add(match outcome {
+            Ok(result) => JsonRpcCallResult::Ok(result),
+            Err(err) => JsonRpcCallResult::Err(err),
+        });
+        sink.close();
+    });
     Ok(())
 }
 
-pub fn send_json_rpc_request(chain_name: String, req: String) -> anyhow::Result<()> {
-    let chains_guard = CHAINS.read();
-    if let Some(chain_id) = chains_guard.get(&chain_name) {
-        // Send a JSON-RPC request to the chain.
-        // Calling this function only queues the request. It is not processed immediately.
-        // An `Err` is returned immediately if and only if the request isn't a proper JSON-RPC request
-        // or if the channel of JSON-RPC responses is clogged.
-        let mut client_lock = CLIENT.lock().unwrap();
-        assert!(client_lock.is_some());
-        let client = client_lock.as_mut().unwrap();
+/// Starts a subscription by calling `method`, and forwards the `result` of each of its
+/// notifications, as JSON text, to `sink`. If the subscription can't be started, the reason is
+/// forwarded instead and the stream ends.
+///
+/// The call is awaited in the background, as with `json_rpc_call`. The subscription is cancelled
+/// by calling `unsubscribe_method` when the Dart stream is cancelled, or when the chain is removed
+/// through `stop_chain_sync`, including if it only starts once the call has timed out. Its
+/// notifications are not forwarded to the stream returned by `listen_json_rpc_responses`.
+pub fn subscribe(
+    chain: RustOpaque<ChainHandle>,
+    method: String,
+    params: String,
+    unsubscribe_method: String,
+    sink: StreamSink<SubscriptionEvent>,
+) -> Result<(), SmoldotFlutterError> {
+    if chain.stopped.load(Ordering::Relaxed) {
+        return Err(SmoldotFlutterError::unknown_chain(&chain.name));
+    }
+    let (light_client, chain_name, instance) =
+        (chain.client.clone(), chain.name.clone(), chain.instance);
+    let sink = Arc::new(sink);
+    async_std::task::spawn(async move {
+        let subscription = Subscription {
+            sink: SubscriptionSink::Stream(sink.clone()),
+            unsubscribe_method,
+        };
+        let outcome = request(
+            &light_client,
+            &chain_name,
+            instance,
+            &method,
+            &params,
+            Some(subscription),
+        );
+        match outcome.await {
+            Ok(subscription_id) => debug!(
+                "Subscription {} started on chain '{:?}'.",
+                subscription_id, chain_name
+            ),
+            Err(err) => {
+                sink.add(SubscriptionEvent::Err(err));
+                sink.close();
+            }
+        }
+    });
+    Ok(())
+}
 
-        client
-            .json_rpc_request(req, *chain_id)
-            .map_err(anyhow::Error::msg)
-            .with_context(|| {
-                format!(
-                    "Failed to enqueue JSON-RPC request to chain '{:?}'.",
-                    chain_name
-                )
-            })?;
-        Ok(())
-    } else {
-        Err(anyhow!("Unknown chain '{:?}'.", chain_name))
+// Sends a JSON-RPC request to the chain and waits for its response, blocking the current thread
+// in the meantime.
+pub(crate) fn call(
+    chain: &ChainHandle,
+    method: &str,
+    params: &str,
+    subscription: Option<Subscription>,
+) -> Result<String, SmoldotFlutterError> {
+    if chain.stopped.load(Ordering::Relaxed) {
+        return Err(SmoldotFlutterError::unknown_chain(&chain.name));
     }
+    async_std::task::block_on(request(
+        &chain.client,
+        &chain.name,
+        chain.instance,
+        method,
+        params,
+        subscription,
+    ))
 }
 
-enum JsonRpcResponse {
-    Disconnected(Option<JsonRpcResponses>),
-    Connected(async_std::task::JoinHandle<()>),
+// Sends a JSON-RPC request to the chain and waits for its response, returning its `result`.
+async fn request(
+    light_client: &LightClient,
+    chain_name: &str,
+    instance: u64,
+    method: &str,
+    params: &str,
+    subscription: Option<Subscription>,
+) -> Result<String, SmoldotFlutterError> {
+    let router = {
+        let chains_guard = light_client.chains.read()?;
+        let chain = find_chain(&chains_guard, chain_name, instance)?;
+        if !chain.json_rpc {
+            return Err(SmoldotFlutterError::json_rpc_disabled(chain_name));
+        }
+        chain.router.clone()
+    };
+    send_and_wait(
+        light_client,
+        chain_name,
+        instance,
+        &router,
+        method,
+        params,
+        subscription,
+    )
+    .await
+}
+
+// Sends a request issued by the crate itself, which the chains whose JSON-RPC is disabled for the
+// app also answer if their database is saved.
+async fn internal_request(
+    light_client: &LightClient,
+    chain_name: &str,
+    instance: u64,
+    method: &str,
+    params: &str,
+) -> Result<String, SmoldotFlutterError> {
+    let router = find_chain(&*light_client.chains.read()?, chain_name, instance)?
+        .router
+        .clone();
+    send_and_wait(
+        light_client,
+        chain_name,
+        instance,
+        &router,
+        method,
+        params,
+        None,
+    )
+    .await
 }
 
+// Sends a request through `router` and waits for its response, returning its `result`.
+async fn send_and_wait(
+    light_client: &LightClient,
+    chain_name: &str,
+    instance: u64,
+    router: &Arc<Router>,
+    method: &str,
+    params: &str,
+    subscription: Option<Subscription>,
+) -> Result<String, SmoldotFlutterError> {
+    let (id, response) = router.register(subscription);
+    let sent = json_rpc::build_request(&id, method, params).and_then(|request| {
+        send_request(light_client, chain_name, instance, Some(router), request)
+    });
+    if let Err(err) = sent {
+        router.cancel(&id);
+        return Err(err);
+    }
+
+    let response = async_std::future::timeout(JSON_RPC_CALL_TIMEOUT, response.recv()).await;
+    match response {
+        Ok(Ok(response)) => json_rpc::parse_result(&response),
+        Ok(Err(_)) => Err(SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::ChainRemoved,
+            "The chain was removed before the response was received.",
+        )),
+        Err(_) => {
+            router.give_up(&id);
+            Err(SmoldotFlutterError::new(
+                SmoldotFlutterErrorKind::Timeout,
+                "Timed out waiting for the response.",
+            ))
+        }
+    }
+    .map_err(|err| {
+        err.context(format_args!(
+            "JSON-RPC call '{}' to chain '{:?}' failed.",
+            method, chain_name
+        ))
+    })
+}
+
+fn initialized<'a>(
+    client_lock: &'a mut MutexGuard<'_, Option<SmoldotClient>>,
+) -> Result<&'a mut SmoldotClient, SmoldotFlutterError> {
+    client_lock
+        .as_mut()
+        .ok_or_else(SmoldotFlutterError::not_initialized)
+}
+
+/// Returns a stream of the JSON-RPC responses and notifications of the chain that aren't
+/// consumed by `json_rpc_call` or `subscribe`.
+///
+/// Any number of streams can listen to the same chain at the same time, and each of them can be
+/// cancelled independently, or all at once through `unlisten_json_rpc_responses`. A stream
+/// attached while others are already listening first receives up to `replay` of the most recent
+/// messages, while a stream attached while none is receives every message produced since the
+/// previous ones were closed, or since the chain was started.
+///
+/// The messages waiting to be forwarded to each stream are buffered as configured by the
+/// `listener_buffer_size` and `overflow_policy` options of the chain. So are the messages produced
+/// while no stream is listening, except that the oldest ones are dropped rather than waiting for a
+/// stream to make room for them.
+///
+/// Only `listener_buffer_size` messages are forwarded to each stream ahead of their
+/// acknowledgement through `ack_json_rpc_responses`, so that a Dart listener slower than the chain
+/// fills up its buffer rather than the memory of the isolate. A stream whose messages are never
+/// acknowledged therefore stops receiving new ones once that many have been delivered.
 pub fn listen_json_rpc_responses(
-    chain_name: String,
+    chain: RustOpaque<ChainHandle>,
+    replay: Option<u32>,
     rpc_responses_sink: StreamSink<String>,
-) -> anyhow::Result<()> {
-    let mut rpc_response_streams_guard = RPC_RESPONSE_STREAMS.write();
-    if let Some(rpc_response) = rpc_response_streams_guard.get_mut(&chain_name) {
-        if let JsonRpcResponse::Disconnected(rpc_responses) = rpc_response {
-            if let Some(mut rpc_responses) = rpc_responses.take() {
-                // Spawn an async task and forward responses received on the channel of JSON-RPC responses
-                // to the response stream sink (towards the Dart side).
-                *rpc_response = JsonRpcResponse::Connected(async_std::task::spawn(async move {
-                    while let Some(response) = rpc_responses.next().await {
-                        debug!(
-                            "JSON-RPC response for chain '{:?}': {}",
-                            chain_name, response
-                        );
-                        rpc_responses_sink.add(response);
-                    }
-                    debug!(
-                        "JSON-RPC response stream for chain '{:?}' has ended.",
-                        chain_name
-                    );
-                }));
+) -> Result<(), SmoldotFlutterError> {
+    let chains_guard = chain.client.chains.read()?;
+    let entry = chain.chain(&chains_guard)?;
+    if !entry.json_rpc {
+        return Err(SmoldotFlutterError::json_rpc_disabled(&chain.name));
+    }
+    entry
+        .broadcaster
+        .attach(rpc_responses_sink, replay.unwrap_or(0) as usize);
+    debug!(
+        "New JSON-RPC response listener for chain '{:?}'.",
+        chain.name
+    );
+    Ok(())
+}
+
+/// Acknowledges that the Dart side has processed `count` more of the messages delivered to each
+/// stream returned by `listen_json_rpc_responses` for the chain, letting as many more be forwarded
+/// to each of them.
+///
+/// The acknowledgement applies to every stream listening to the chain, so messages should only be
+/// acknowledged once each of those streams has processed them.
+pub fn ack_json_rpc_responses(
+    chain: RustOpaque<ChainHandle>,
+    count: u32,
+) -> Result<(), SmoldotFlutterError> {
+    let chains_guard = chain.client.chains.read()?;
+    let entry = chain.chain(&chains_guard)?;
+    if !entry.json_rpc {
+        return Err(SmoldotFlutterError::json_rpc_disabled(&chain.name));
+    }
+    entry.broadcaster.acknowledge(count as usize);
+    Ok(())
+}
+
+/// Closes every stream returned by `listen_json_rpc_responses` for the chain, once the messages
+/// already buffered for them have been delivered.
+///
+/// The messages produced afterwards are kept for the next stream to listen to the chain, as are
+/// the ones produced once every stream has been cancelled from the Dart side.
+pub fn unlisten_json_rpc_responses(
+    chain: RustOpaque<ChainHandle>,
+) -> Result<(), SmoldotFlutterError> {
+    let chains_guard = chain.client.chains.read()?;
+    let entry = chain.chain(&chains_guard)?;
+    if !entry.json_rpc {
+        return Err(SmoldotFlutterError::json_rpc_disabled(&chain.name));
+    }
+    let detached = entry.broadcaster.detach_all();
+    debug!(
+        "{} JSON-RPC response listener(s) detached from chain '{:?}'.",
+        detached, chain.name
+    );
+    Ok(())
+}
+
+/// Returns statistics about the delivery of the JSON-RPC responses of the chain to the streams
+/// returned by `listen_json_rpc_responses`.
+pub fn json_rpc_metrics(
+    chain: RustOpaque<ChainHandle>,
+) -> Result<JsonRpcMetrics, SmoldotFlutterError> {
+    let chains_guard = chain.client.chains.read()?;
+    let broadcaster = &chain.chain(&chains_guard)?.broadcaster;
+    Ok(JsonRpcMetrics {
+        dropped_messages: broadcaster.dropped_messages(),
+        buffered_messages: broadcaster.buffered_messages() as u64,
+    })
+}
+
+/// Forwards the sync status of the chain to `sink`, once it is first known, then whenever it
+/// changes and after every health check. If the status can't be followed, the reason is forwarded
+/// instead and the stream ends.
+///
+/// The status is followed through the `chain_subscribeNewHeads` and
+/// `chain_subscribeFinalizedHeads` subscriptions of the chain, which are started in the
+/// background as with `subscribe`, and through `system_health`, which is called on every new best
+/// block and every few seconds otherwise. Forwarding the status after every health check, even if
+/// it is unchanged, lets the subscriptions be cancelled within a few seconds of the Dart stream.
+/// The stream carries on if the chain is restarted through `restart_chain_sync` or
+/// `import_chain_database`, and ends once the chain is stopped.
+pub fn watch_chain_status(
+    chain: RustOpaque<ChainHandle>,
+    sink: StreamSink<ChainStatusEvent>,
+) -> Result<(), SmoldotFlutterError> {
+    if chain.stopped.load(Ordering::Relaxed) {
+        return Err(SmoldotFlutterError::unknown_chain(&chain.name));
+    }
+    async_std::task::spawn(watch_status(
+        chain.client.clone(),
+        chain.name.clone(),
+        chain.instance,
+        sink,
+    ));
+    Ok(())
+}
+
+enum Head {
+    Best(serde_json::Value),
+    Finalized(serde_json::Value),
+}
+
+// Subscribes to the new best and finalized blocks of the chain, whose headers are delivered by
+// the returned stream. The subscriptions are cancelled once the stream is dropped.
+async fn subscribe_heads(
+    light_client: &LightClient,
+    chain_name: &str,
+    instance: u64,
+) -> Result<BoxStream<'static, Head>, SmoldotFlutterError> {
+    let mut receivers = Vec::with_capacity(2);
+    for (method, unsubscribe_method) in [
+        ("chain_subscribeNewHeads", "chain_unsubscribeNewHeads"),
+        (
+            "chain_subscribeFinalizedHeads",
+            "chain_unsubscribeFinalizedHeads",
+        ),
+    ] {
+        let (sender, receiver) = async_std::channel::unbounded();
+        let subscription = Subscription {
+            sink: SubscriptionSink::Task(sender),
+            unsubscribe_method: unsubscribe_method.into(),
+        };
+        request(
+            light_client,
+            chain_name,
+            instance,
+            method,
+            "[]",
+            Some(subscription),
+        )
+        .await?;
+        receivers.push(receiver);
+    }
+    let finalized = receivers.pop().unwrap();
+    let best = receivers.pop().unwrap();
+    Ok(stream::select(best.map(Head::Best), finalized.map(Head::Finalized)).boxed())
+}
+
+// Follows the status of the chain for `watch_chain_status`, until the chain is removed or the Dart
+// stream is cancelled.
+async fn watch_status(
+    light_client: RustOpaque<LightClient>,
+    chain_name: String,
+    instance: u64,
+    sink: StreamSink<ChainStatusEvent>,
+) {
+    let mut heads = match subscribe_heads(&light_client, &chain_name, instance).await {
+        Ok(heads) => heads,
+        Err(err) => {
+            sink.add(ChainStatusEvent::Err(err));
+            sink.close();
+            return;
+        }
+    };
+    let mut tracker = StatusTracker::new();
+    let mut forwarded = None;
+    loop {
+        // The first status is forwarded right away
+        let head = match forwarded {
+            None => None,
+            Some(_) => {
+                match async_std::future::timeout(HEALTH_CHECK_INTERVAL, heads.next()).await {
+                    Ok(Some(head)) => Some(head),
+                    // The subscriptions are closed when the chain is removed, including when it is
+                    // restarted, in which case they are started again
+                    Ok(None) => match subscribe_heads(&light_client, &chain_name, instance).await {
+                        Ok(new_heads) => {
+                            heads = new_heads;
+                            continue;
+                        }
+                        Err(_) => break,
+                    },
+                    Err(_) => None,
+                }
+            }
+        };
+        let check_health = match head {
+            Some(Head::Best(header)) => {
+                tracker.best_head(&header);
+                true
+            }
+            Some(Head::Finalized(header)) => {
+                tracker.finalized_head(&header);
+                false
+            }
+            None => true,
+        };
+        if check_health {
+            let health = request(
+                &light_client,
+                &chain_name,
+                instance,
+                "system_health",
+                "[]",
+                None,
+            );
+            match health.await {
+                Ok(health) => tracker.health(&health),
+                Err(err) => debug!(
+                    "Failed to check health of chain '{:?}': {}",
+                    chain_name, err
+                ),
             }
         }
-        Ok(())
-    } else {
-        Err(anyhow!("Unknown chain '{:?}'.", chain_name))
+        // The status is also forwarded after every health check, even if unchanged, since adding
+        // to the sink is the only way to notice that the Dart stream was cancelled
+        if check_health || forwarded.as_ref() != Some(tracker.status()) {
+            if !sink.add(ChainStatusEvent::Status(tracker.status().clone())) {
+                // Dropping the stream of headers cancels the subscriptions
+                return;
+            }
+            forwarded = Some(tracker.status().clone());
+        }
     }
+    sink.close();
+}
+
+/// Starts a JSON-RPC server serving the chains of the default light client over WebSocket, for
+/// development tools to connect to, and returns the port it listens on.
+///
+/// The server only listens on the loopback interface, on `port` or on a port picked by the OS if
+/// `None`. A connection to `ws://127.0.0.1:<port>/<chain_name>` is bound to the chain started under
+/// the given name, and only receives the responses to its own requests and the notifications of
+/// its own subscriptions. Any server previously started on the client is stopped first.
+///
+/// A connection follows the chain through `restart_chain_sync` and `import_chain_database`,
+/// except that its subscriptions end, each with a `smoldotFlutter_subscriptionClosed`
+/// notification whose `params` hold the id of the `subscription`. Once the chain is stopped, its
+/// connections are closed.
+///
+/// Web pages opened in a browser on the device could connect to the server as well, and are told
+/// apart by the `Origin` header browsers send: their connections are rejected, unless their origin
+/// (such as `https://polkadot.js.org`) is among `allowed_origins`.
+///
+/// Only available if the crate is built with its `server` feature.
+pub fn start_json_rpc_server(
+    port: Option<u16>,
+    allowed_origins: Vec<String>,
+) -> Result<u16, SmoldotFlutterError> {
+    start_server(&DEFAULT_CLIENT, port, allowed_origins)
+}
+
+/// Starts a JSON-RPC server serving the chains of the given light client, like
+/// `start_json_rpc_server` does for the default one.
+///
+/// The client isn't shut down when dropped for as long as its server is running.
+pub fn light_client_start_json_rpc_server(
+    client: RustOpaque<LightClient>,
+    port: Option<u16>,
+    allowed_origins: Vec<String>,
+) -> Result<u16, SmoldotFlutterError> {
+    start_server(&client, port, allowed_origins)
+}
+
+#[cfg(feature = "server")]
+fn start_server(
+    light_client: &RustOpaque<LightClient>,
+    port: Option<u16>,
+    allowed_origins: Vec<String>,
+) -> Result<u16, SmoldotFlutterError> {
+    let mut server_lock = light_client.server.lock()?;
+    // Stop the previous server first, so that its port can be reused
+    *server_lock = None;
+
+    let endpoint_client = light_client.clone();
+    let server = JsonRpcServer::start(port.unwrap_or(0), allowed_origins, move |chain_name| {
+        endpoint(&endpoint_client, chain_name)
+    })
+    .map_err(|err| {
+        SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::ServerUnavailable,
+            format!("Failed to start the JSON-RPC server: {}", err),
+        )
+    })?;
+    let port = server.port();
+    *server_lock = Some(server);
+    debug!("JSON-RPC server listening on port {}.", port);
+    Ok(port)
+}
+
+#[cfg(not(feature = "server"))]
+fn start_server(
+    _: &LightClient,
+    _: Option<u16>,
+    _: Vec<String>,
+) -> Result<u16, SmoldotFlutterError> {
+    Err(SmoldotFlutterError::new(
+        SmoldotFlutterErrorKind::ServerUnavailable,
+        "The JSON-RPC server requires the `server` feature.",
+    ))
+}
+
+// Binds a connection to the server to the chain with the given name. Its requests are sent to the
+// chain as it runs at the time, which changes whenever the chain is restarted, until it is
+// stopped.
+#[cfg(feature = "server")]
+fn endpoint(light_client: &RustOpaque<LightClient>, chain_name: &str) -> Option<Endpoint> {
+    let instance = light_client
+        .chains
+        .read()
+        .ok()?
+        .get(chain_name)
+        .filter(|chain| chain.json_rpc)?
+        .instance;
+    let router_client = light_client.clone();
+    let router_chain_name = chain_name.to_owned();
+    let light_client = light_client.clone();
+    let chain_name = chain_name.to_owned();
+    Some(Endpoint {
+        router: Box::new(move || {
+            let chains_guard = router_client.chains.read().ok()?;
+            let chain = find_chain(&chains_guard, &router_chain_name, instance).ok()?;
+            chain.json_rpc.then(|| chain.router.clone())
+        }),
+        send_request: Box::new(move |router, req| {
+            send_request(&light_client, &chain_name, instance, Some(router), req)
+        }),
+    })
+}
+
+/// Stops the JSON-RPC server of the default light client, closing its connections.
+///
+/// Does nothing if the server isn't running.
+pub fn stop_json_rpc_server() -> Result<(), SmoldotFlutterError> {
+    stop_server(&DEFAULT_CLIENT)
+}
+
+/// Stops the JSON-RPC server of the given light client, like `stop_json_rpc_server` does for the
+/// default one.
+pub fn light_client_stop_json_rpc_server(
+    client: RustOpaque<LightClient>,
+) -> Result<(), SmoldotFlutterError> {
+    stop_server(&client)
+}
+
+#[cfg(feature = "server")]
+fn stop_server(light_client: &LightClient) -> Result<(), SmoldotFlutterError> {
+    if light_client.server.lock()?.take().is_some() {
+        debug!("JSON-RPC server stopped.");
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "server"))]
+fn stop_server(_: &LightClient) -> Result<(), SmoldotFlutterError> {
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::platform::tests::deterministic_platform;
     use std::fs;
 
-    // IMPORTANT: tests must be executed one by one.
+    // Every test uses its own client instance, apart from the one exercising the default client,
+    // so that the tests can run in parallel.
+    fn create_test_client() -> RustOpaque<LightClient> {
+        LightClient::with_platform(deterministic_platform().0, None)
+    }
+
+    // Loads a chain specification without its boot nodes, so that the chain only answers the
+    // requests that can be served without connecting to any peer.
+    fn chain_spec(name: &str) -> String {
+        let chain_spec = fs::read_to_string(format!("../assets/chainspecs/{}.json", name)).unwrap();
+        let mut chain_spec: serde_json::Value = serde_json::from_str(&chain_spec).unwrap();
+        chain_spec["bootNodes"] = serde_json::Value::Array(Vec::new());
+        chain_spec.to_string()
+    }
+
+    fn start(
+        client: &RustOpaque<LightClient>,
+        chain_name: &str,
+        relay_chains: &[&str],
+    ) -> Result<RustOpaque<ChainHandle>, SmoldotFlutterError> {
+        light_client_start_chain_sync(
+            client.clone(),
+            chain_name.into(),
+            chain_spec(&chain_name.to_lowercase()),
+            "".into(),
+            relay_chains.iter().map(|&name| name.into()).collect(),
+            None,
+        )
+    }
+
+    fn system_chain(chain: &RustOpaque<ChainHandle>) -> Result<String, SmoldotFlutterError> {
+        call(chain, "system_chain", "[]", None)
+    }
+
+    // Returns whether the stream ends within a few seconds.
+    async fn ended(heads: BoxStream<'static, Head>) -> bool {
+        async_std::future::timeout(Duration::from_secs(5), heads.count())
+            .await
+            .is_ok()
+    }
+
+    #[test]
+    fn syncs_kusama_relay_chain() {
+        let client = create_test_client();
+
+        let relay_chain = start(&client, "Kusama", &[]).unwrap();
+        assert_eq!(system_chain(&relay_chain).unwrap(), r#""Kusama""#);
+        stop_chain_sync(relay_chain, None).unwrap();
+    }
 
     #[test]
-    fn syncs_polkadot_relay_chain() {
-        init_light_client().unwrap();
+    fn syncs_statemine_parachain() {
+        let client = create_test_client();
+
+        let relay_chain = start(&client, "Kusama", &[]).unwrap();
+        let parachain = start(&client, "Statemine", &["Kusama"]).unwrap();
+        assert_eq!(system_chain(&parachain).unwrap(), r#""Statemine""#);
 
-        let relay_chain = String::from("Polkadot");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/polkadot.json").unwrap();
-        start_chain_sync(relay_chain.clone(), chain_spec, "".into(), None).unwrap();
-        stop_chain_sync(relay_chain).unwrap();
+        stop_chain_sync(parachain, None).unwrap();
+        stop_chain_sync(relay_chain, None).unwrap();
     }
 
     #[test]
-    fn syncs_statemint_parachain() {
-        init_light_client().unwrap();
+    fn syncs_rococo_relay_chain() {
+        let client = create_test_client();
 
-        let relay_chain = String::from("Polkadot");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/polkadot.json").unwrap();
-        start_chain_sync(relay_chain.clone(), chain_spec, "".into(), None).unwrap();
+        let relay_chain = start(&client, "Rococo", &[]).unwrap();
+        assert_eq!(system_chain(&relay_chain).unwrap(), r#""Rococo""#);
+        stop_chain_sync(relay_chain, None).unwrap();
+    }
+
+    #[test]
+    fn syncs_rockmine_parachain() {
+        let client = create_test_client();
+
+        let relay_chain = start(&client, "Rococo", &[]).unwrap();
+        let parachain = start(&client, "Rockmine", &["Rococo"]).unwrap();
+        assert_eq!(system_chain(&parachain).unwrap(), r#""Rockmine""#);
 
-        let parachain = String::from("Statemint");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/statemint.json").unwrap();
-        start_chain_sync(
+        stop_chain_sync(parachain, None).unwrap();
+        stop_chain_sync(relay_chain, None).unwrap();
+    }
+
+    #[test]
+    fn picks_relay_chain_among_candidates() {
+        let client = create_test_client();
+
+        let kusama = start(&client, "Kusama", &[]).unwrap();
+        let rococo = start(&client, "Rococo", &[]).unwrap();
+        let parachain = start(&client, "Rockmine", &["Kusama", "Rococo"]).unwrap();
+        assert_eq!(system_chain(&parachain).unwrap(), r#""Rockmine""#);
+
+        stop_chain_sync(parachain, None).unwrap();
+        stop_chain_sync(rococo, None).unwrap();
+        stop_chain_sync(kusama, None).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_relay_chain() {
+        let client = create_test_client();
+
+        let err = start(&client, "Rockmine", &["Rococo"]).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::RelayChainNotFound);
+
+        let relay_chain = start(&client, "Kusama", &[]).unwrap();
+        let err = start(&client, "Rockmine", &["Kusama"]).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::RelayChainNotFound);
+        stop_chain_sync(relay_chain, None).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_chain_spec() {
+        let client = create_test_client();
+
+        let err = light_client_start_chain_sync(
+            client,
+            "Kusama".into(),
+            "{}".into(),
+            "".into(),
+            vec![],
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::InvalidChainSpec);
+    }
+
+    #[test]
+    fn shares_chains_started_twice() {
+        let client = create_test_client();
+
+        let first = start(&client, "Kusama", &[]).unwrap();
+        let second = start(&client, "Kusama", &[]).unwrap();
+
+        let err = light_client_start_chain_sync(
+            client.clone(),
+            "Kusama".into(),
+            chain_spec("rococo"),
+            "".into(),
+            vec![],
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::DuplicateChain);
+
+        stop_chain_sync(first.clone(), None).unwrap();
+        system_chain(&second).unwrap();
+        let err = system_chain(&first).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::UnknownChain);
+        let err = stop_chain_sync(first, None).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::UnknownChain);
+
+        drop(second);
+        let third = light_client_start_chain_sync(
+            client,
+            "Kusama".into(),
+            chain_spec("rococo"),
+            "".into(),
+            vec![],
+            None,
+        )
+        .unwrap();
+        assert_eq!(system_chain(&third).unwrap(), r#""Rococo""#);
+        stop_chain_sync(third, None).unwrap();
+    }
+
+    #[test]
+    fn applies_chain_options() {
+        let client = create_test_client();
+        let relay_only = || ChainOptions {
+            disable_json_rpc: Some(true),
+            ..Default::default()
+        };
+
+        let relay_chain = light_client_start_chain_sync(
+            client.clone(),
+            "Kusama".into(),
+            chain_spec("kusama"),
+            "".into(),
+            vec![],
+            Some(relay_only()),
+        )
+        .unwrap();
+        let err = system_chain(&relay_chain).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::JsonRpcDisabled);
+        let err = send_json_rpc_request(
+            relay_chain.clone(),
+            r#"{"jsonrpc":"2.0","id":"1","method":"system_chain","params":[]}"#.into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::JsonRpcDisabled);
+        let err = unlisten_json_rpc_responses(relay_chain.clone()).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::JsonRpcDisabled);
+        let err = start(&client, "Kusama", &[]).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::DuplicateChain);
+        // Without a database directory, the chain doesn't even have a JSON-RPC service
+        assert!(!client.chains.read().unwrap()["Kusama"].json_rpc_service);
+
+        let parachain = start(&client, "Statemine", &["Kusama"]).unwrap();
+        assert_eq!(system_chain(&parachain).unwrap(), r#""Statemine""#);
+        unlisten_json_rpc_responses(parachain.clone()).unwrap();
+        restart_chain_sync(
             parachain.clone(),
-            chain_spec,
+            chain_spec("statemine"),
             "".into(),
-            Some(relay_chain.clone()),
+            vec!["Kusama".into()],
+            Some(ChainOptions {
+                max_subscriptions: Some(0),
+                ..Default::default()
+            }),
         )
         .unwrap();
+        let err = call(&parachain, "chain_subscribeNewHeads", "[]", None).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::JsonRpcError);
+        let metrics = json_rpc_metrics(parachain.clone()).unwrap();
+        assert_eq!(metrics.dropped_messages, 0);
+        assert_eq!(metrics.buffered_messages, 0);
 
-        stop_chain_sync(relay_chain).unwrap();
-        stop_chain_sync(parachain).unwrap();
+        stop_chain_sync(parachain, None).unwrap();
+        stop_chain_sync(relay_chain, None).unwrap();
     }
 
     #[test]
-    fn syncs_kusama_relay_chain() {
-        init_light_client().unwrap();
+    fn restarts_chain_behind_its_handles() {
+        let client = create_test_client();
+
+        let first = start(&client, "Kusama", &[]).unwrap();
+        let second = start(&client, "Kusama", &[]).unwrap();
+        let old_router = first
+            .chain(&client.chains.read().unwrap())
+            .unwrap()
+            .router
+            .clone();
+        restart_chain_sync(first.clone(), chain_spec("rococo"), "".into(), vec![], None).unwrap();
+        assert_eq!(system_chain(&first).unwrap(), r#""Rococo""#);
+        assert_eq!(system_chain(&second).unwrap(), r#""Rococo""#);
+
+        // The requests registered with the router of the old chain don't reach the new one.
+        let (id, _) = old_router.register(None);
+        let request = json_rpc::build_request(&id, "system_chain", "[]").unwrap();
+        let err = send_request(
+            &client,
+            "Kusama",
+            first.instance,
+            Some(&old_router),
+            request,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::ChainRemoved);
+
+        let err =
+            restart_chain_sync(first.clone(), "{}".into(), "".into(), vec![], None).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::InvalidChainSpec);
+        assert_eq!(system_chain(&first).unwrap(), r#""Rococo""#);
 
-        let relay_chain = String::from("Kusama");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/kusama.json").unwrap();
-        start_chain_sync(relay_chain.clone(), chain_spec, "".into(), None).unwrap();
-        stop_chain_sync(relay_chain).unwrap();
+        stop_chain_sync(first, None).unwrap();
+        stop_chain_sync(second, None).unwrap();
     }
 
     #[test]
-    fn syncs_statemine_parachain() {
-        init_light_client().unwrap();
+    fn round_trips_json_rpc_calls() {
+        let client = create_test_client();
+        let chain = start(&client, "Kusama", &[]).unwrap();
 
-        let relay_chain = String::from("Kusama");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/kusama.json").unwrap();
-        start_chain_sync(relay_chain.clone(), chain_spec, "".into(), None).unwrap();
+        let health = call(&chain, "system_health", "[]", None).unwrap();
+        let health: serde_json::Value = serde_json::from_str(&health).unwrap();
+        assert_eq!(health["peers"], 0);
 
-        let parachain = String::from("Statemine");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/statemine.json").unwrap();
-        start_chain_sync(
-            parachain.clone(),
-            chain_spec,
+        let err = call(&chain, "no_such_method", "[]", None).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::JsonRpcError);
+        let err = call(&chain, "system_chain", "0", None).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::InvalidRequest);
+
+        send_json_rpc_request(
+            chain.clone(),
+            r#"{"jsonrpc":"2.0","id":"1","method":"system_chain","params":[]}"#.into(),
+        )
+        .unwrap();
+        let err = send_json_rpc_request(chain.clone(), "{".into()).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::InvalidRequest);
+        let err = send_json_rpc_request(
+            chain.clone(),
+            r#"{"jsonrpc":"2.0","id":"smoldot-flutter:0","method":"system_chain","params":[]}"#
+                .into(),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::InvalidRequest);
+
+        stop_chain_sync(chain, None).unwrap();
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn serves_chains_over_websocket() {
+        use soketto::handshake::{client::Header, Client, ServerResponse};
+
+        let client = create_test_client();
+        let chain = start(&client, "Kusama", &[]).unwrap();
+        let port = light_client_start_json_rpc_server(
+            client.clone(),
+            None,
+            vec!["https://polkadot.js.org".into()],
+        )
+        .unwrap();
+
+        async_std::task::block_on(async {
+            let connect = |path, headers: &'static [Header<'static>]| async move {
+                let stream = async_std::net::TcpStream::connect(("127.0.0.1", port))
+                    .await
+                    .unwrap();
+                let mut handshake = Client::new(stream, "127.0.0.1", path);
+                handshake.set_headers(headers);
+                let response = handshake.handshake().await.unwrap();
+                (response, handshake)
+            };
+            let (response, _) = connect("/Polkadot", &[]).await;
+            assert!(matches!(
+                response,
+                ServerResponse::Rejected { status_code: 404 }
+            ));
+            let (response, _) = connect(
+                "/Kusama",
+                &[Header {
+                    name: "Origin",
+                    value: b"https://example.com",
+                }],
+            )
+            .await;
+            assert!(matches!(
+                response,
+                ServerResponse::Rejected { status_code: 403 }
+            ));
+            let (response, _) = connect(
+                "/Kusama",
+                &[Header {
+                    name: "Origin",
+                    value: b"https://polkadot.js.org",
+                }],
+            )
+            .await;
+            assert!(matches!(response, ServerResponse::Accepted { .. }));
+
+            let (response, handshake) = connect("/Kusama", &[]).await;
+            assert!(matches!(response, ServerResponse::Accepted { .. }));
+            let (mut sender, mut receiver) = handshake.into_builder().finish();
+            let mut responses = Vec::new();
+            for request in [
+                r#"{"jsonrpc":"2.0","id":1,"method":"system_chain","params":[]}"#,
+                r#"[
+                    {"jsonrpc":"2.0","id":"a","method":"system_chain","params":[]},
+                    {"jsonrpc":"2.0","id":"b","method":"system_name","params":[]}
+                ]"#,
+            ] {
+                sender.send_text(request).await.unwrap();
+                sender.flush().await.unwrap();
+                let mut response = Vec::new();
+                receiver.receive_data(&mut response).await.unwrap();
+                responses.push(serde_json::from_slice::<serde_json::Value>(&response).unwrap());
+            }
+
+            assert_eq!(
+                responses[0],
+                serde_json::json!({"jsonrpc":"2.0","id":1,"result":"Kusama"})
+            );
+            let response = &responses[1];
+            assert_eq!(response[0]["id"], "a");
+            assert_eq!(response[0]["result"], "Kusama");
+            assert_eq!(response[1]["id"], "b");
+            assert!(response[1]["result"].is_string());
+
+            // The connection follows the chain when it is restarted, which only ends its
+            // subscriptions, and is closed once the chain is stopped.
+            let request =
+                r#"{"jsonrpc":"2.0","id":3,"method":"chain_subscribeNewHeads","params":[]}"#;
+            sender.send_text(request).await.unwrap();
+            sender.flush().await.unwrap();
+            let subscription_id = loop {
+                let mut message = Vec::new();
+                receiver.receive_data(&mut message).await.unwrap();
+                let message = serde_json::from_slice::<serde_json::Value>(&message).unwrap();
+                if message["id"] == 3 {
+                    break message["result"].clone();
+                }
+            };
+            assert!(subscription_id.is_string());
+            restart_chain_sync(chain.clone(), chain_spec("kusama"), "".into(), vec![], None)
+                .unwrap();
+            loop {
+                let mut message = Vec::new();
+                receiver.receive_data(&mut message).await.unwrap();
+                let message = serde_json::from_slice::<serde_json::Value>(&message).unwrap();
+                if message["method"] == "smoldotFlutter_subscriptionClosed" {
+                    assert_eq!(message["params"]["subscription"], subscription_id);
+                    break;
+                }
+                assert_eq!(message["method"], "chain_newHead");
+            }
+            let request = r#"{"jsonrpc":"2.0","id":2,"method":"system_chain","params":[]}"#;
+            sender.send_text(request).await.unwrap();
+            sender.flush().await.unwrap();
+            let mut response = Vec::new();
+            receiver.receive_data(&mut response).await.unwrap();
+            assert_eq!(
+                serde_json::from_slice::<serde_json::Value>(&response).unwrap(),
+                serde_json::json!({"jsonrpc":"2.0","id":2,"result":"Kusama"})
+            );
+
+            stop_chain_sync(chain.clone(), None).unwrap();
+            sender.send_text(request).await.unwrap();
+            sender.flush().await.unwrap();
+            assert!(receiver.receive_data(&mut Vec::new()).await.is_err());
+        });
+
+        light_client_stop_json_rpc_server(client).unwrap();
+    }
+
+    #[test]
+    fn persists_chain_databases() {
+        let store = database::tests::store("persists_chain_databases", Duration::from_secs(3600));
+        let client = LightClient::with_platform(deterministic_platform().0, Some(store.clone()));
+
+        let chain = start(&client, "Kusama", &[]).unwrap();
+        save_databases_now(
+            &client,
+            &[("Kusama".into(), chain.instance)],
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        let saved = store
+            .load("Kusama", &chain_spec("kusama"), &OnceLock::new())
+            .unwrap();
+        assert!(saved.starts_with('{'));
+        stop_chain_sync(chain, None).unwrap();
+
+        let chain = start(&client, "Kusama", &[]).unwrap();
+        assert_eq!(system_chain(&chain).unwrap(), r#""Kusama""#);
+        stop_chain_sync(chain, None).unwrap();
+        database::tests::remove_store(&store);
+    }
+
+    #[test]
+    fn saves_databases_when_stopping() {
+        let store =
+            database::tests::store("saves_databases_when_stopping", Duration::from_secs(3600));
+        let client = LightClient::with_platform(deterministic_platform().0, Some(store.clone()));
+
+        let chain = start(&client, "Kusama", &[]).unwrap();
+        stop_chain_sync(chain, Some(10_000)).unwrap();
+        assert!(store
+            .load("Kusama", &chain_spec("kusama"), &OnceLock::new())
+            .is_some());
+
+        // The chains whose JSON-RPC is disabled are saved as well
+        let rococo = light_client_start_chain_sync(
+            client.clone(),
+            "Rococo".into(),
+            chain_spec("rococo"),
             "".into(),
-            Some(relay_chain.clone()),
+            vec![],
+            Some(ChainOptions {
+                disable_json_rpc: Some(true),
+                ..Default::default()
+            }),
         )
         .unwrap();
+        let kusama = start(&client, "Kusama", &[]).unwrap();
+        let err = system_chain(&rococo).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::JsonRpcDisabled);
+        // Saving them takes a JSON-RPC service the app can't use
+        assert!(client.chains.read().unwrap()["Rococo"].json_rpc_service);
+        database::tests::remove_store(&store);
+        light_client_suspend_all_chains(client.clone(), 10_000).unwrap();
+        assert!(store
+            .load("Rococo", &chain_spec("rococo"), &OnceLock::new())
+            .is_some());
+        assert!(store
+            .load("Kusama", &chain_spec("kusama"), &OnceLock::new())
+            .is_some());
+        let err = system_chain(&rococo).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::UnknownChain);
+        drop((rococo, kusama));
 
-        stop_chain_sync(relay_chain).unwrap();
-        stop_chain_sync(parachain).unwrap();
+        // Chains can be started again afterwards
+        let chain = start(&client, "Kusama", &[]).unwrap();
+        assert_eq!(system_chain(&chain).unwrap(), r#""Kusama""#);
+        stop_chain_sync(chain, None).unwrap();
+        database::tests::remove_store(&store);
     }
 
     #[test]
-    fn syncs_rococo_relay_chain() {
-        init_light_client().unwrap();
+    fn exports_and_imports_databases() {
+        let client = create_test_client();
+        let relay_chain = start(&client, "Kusama", &[]).unwrap();
+        let parachain = start(&client, "Statemine", &["Kusama"]).unwrap();
+
+        // Without any peer, the finalized block of the relay chain remains its checkpoint
+        let database = export_chain_database(relay_chain.clone(), None).unwrap();
+        let import = import_chain_database(relay_chain.clone(), database).unwrap();
+        assert_eq!(import, DatabaseImport::Outdated);
+        let import = import_chain_database(relay_chain.clone(), "{}".into()).unwrap();
+        assert_eq!(import, DatabaseImport::Malformed);
+        let database = export_chain_database(parachain.clone(), None).unwrap();
+        let import = import_chain_database(relay_chain.clone(), database).unwrap();
+        assert_eq!(import, DatabaseImport::WrongChain);
+
+        // A database ahead of the checkpoint is accepted, unless the light client rejects it
+        let ahead = database::tests::checkpoint_database(&chain_spec("kusama"), 1);
+        let mut rejected: serde_json::Value = serde_json::from_str(&ahead).unwrap();
+        rejected.as_object_mut().unwrap().remove("nodes");
+        let import = import_chain_database(relay_chain.clone(), rejected.to_string()).unwrap();
+        assert_eq!(import, DatabaseImport::Malformed);
+        let import = import_chain_database(relay_chain.clone(), ahead).unwrap();
+        assert_eq!(import, DatabaseImport::Accepted);
+        assert_eq!(system_chain(&relay_chain).unwrap(), r#""Kusama""#);
 
-        let relay_chain = String::from("Rococo");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/rococo.json").unwrap();
-        start_chain_sync(relay_chain.clone(), chain_spec, "".into(), None).unwrap();
-        stop_chain_sync(relay_chain).unwrap();
+        // Without a checkpoint, the chain starts from its genesis block, which any database of
+        // the chain is at least as recent as. The light client would share the relay chain with
+        // a chain of the same genesis block, so the chain is started on a client of its own.
+        let mut chain_spec: serde_json::Value =
+            serde_json::from_str(&chain_spec("kusama")).unwrap();
+        chain_spec.as_object_mut().unwrap().remove("lightSyncState");
+        let genesis_chain = light_client_start_chain_sync(
+            create_test_client(),
+            "Kusama from genesis".into(),
+            chain_spec.to_string(),
+            "".into(),
+            vec![],
+            None,
+        )
+        .unwrap();
+        let database = export_chain_database(genesis_chain.clone(), Some(1_000_000)).unwrap();
+        let import = import_chain_database(relay_chain.clone(), database.clone()).unwrap();
+        assert_eq!(import, DatabaseImport::Outdated);
+        let import = import_chain_database(genesis_chain.clone(), database).unwrap();
+        assert_eq!(import, DatabaseImport::Accepted);
+        assert_eq!(system_chain(&genesis_chain).unwrap(), r#""Kusama""#);
+
+        stop_chain_sync(genesis_chain.clone(), None).unwrap();
+        let err = export_chain_database(genesis_chain, None).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::UnknownChain);
+        stop_chain_sync(parachain, None).unwrap();
+        stop_chain_sync(relay_chain, None).unwrap();
     }
 
     #[test]
-    fn syncs_rockmine_parachain() {
-        init_light_client().unwrap();
+    fn isolates_light_client_instances() {
+        let first_client = create_test_client();
+        let second_client = create_test_client();
 
-        let relay_chain = String::from("rococo");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/rococo.json").unwrap();
-        start_chain_sync(relay_chain.clone(), chain_spec, "".into(), None).unwrap();
+        let first = start(&first_client, "Kusama", &[]).unwrap();
+        let err = start(&second_client, "Statemine", &["Kusama"]).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::RelayChainNotFound);
+        let second = light_client_start_chain_sync(
+            second_client.clone(),
+            "Kusama".into(),
+            chain_spec("rococo"),
+            "".into(),
+            vec![],
+            None,
+        )
+        .unwrap();
 
-        let parachain = String::from("Rockmine");
-        let chain_spec = fs::read_to_string("../assets/chainspecs/rockmine.json").unwrap();
-        start_chain_sync(
-            parachain.clone(),
-            chain_spec,
+        light_client_shutdown(first_client.clone()).unwrap();
+        let err = system_chain(&first).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::UnknownChain);
+        let err = start(&first_client, "Kusama", &[]).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::NotInitialized);
+        assert_eq!(system_chain(&second).unwrap(), r#""Rococo""#);
+
+        stop_chain_sync(second, None).unwrap();
+        light_client_shutdown(second_client).unwrap();
+    }
+
+    #[test]
+    fn follows_heads_until_chain_is_removed() {
+        let client = create_test_client();
+        let relay_chain = start(&client, "Kusama", &[]).unwrap();
+        let err = async_std::task::block_on(subscribe_heads(&client, "Statemine", 0))
+            .err()
+            .unwrap();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::UnknownChain);
+
+        let heads =
+            async_std::task::block_on(subscribe_heads(&client, "Kusama", relay_chain.instance))
+                .unwrap();
+        restart_chain_sync(
+            relay_chain.clone(),
+            chain_spec("kusama"),
             "".into(),
-            Some(relay_chain.clone()),
+            vec![],
+            None,
         )
         .unwrap();
+        // The restart ends the subscriptions, which can be started again
+        assert!(async_std::task::block_on(ended(heads)));
+        let heads =
+            async_std::task::block_on(subscribe_heads(&client, "Kusama", relay_chain.instance))
+                .unwrap();
+        stop_chain_sync(relay_chain, None).unwrap();
+        assert!(async_std::task::block_on(ended(heads)));
+    }
+
+    #[test]
+    fn initializes_default_client_once() {
+        let err = start_chain_sync(
+            "Kusama".into(),
+            chain_spec("kusama"),
+            "".into(),
+            vec![],
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::NotInitialized);
 
-        stop_chain_sync(relay_chain).unwrap();
-        stop_chain_sync(parachain).unwrap();
+        init_light_client(None).unwrap();
+        let err = init_light_client(None).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::AlreadyInitialized);
+        let chain = start_chain_sync(
+            "Kusama".into(),
+            chain_spec("kusama"),
+            "".into(),
+            vec![],
+            None,
+        )
+        .unwrap();
+        assert_eq!(system_chain(&chain).unwrap(), r#""Kusama""#);
+
+        shutdown_light_client().unwrap();
+        let err = system_chain(&chain).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::UnknownChain);
+        shutdown_light_client().unwrap();
+
+        init_light_client(Some(LightClientConfig {
+            client_name: Some("smoldot-flutter-tests".into()),
+            allow_tcp: Some(false),
+            ..Default::default()
+        }))
+        .unwrap();
+        drop(chain);
+        shutdown_light_client().unwrap();
     }
 }
@@ -0,0 +1,313 @@
+//! Drives the light client from a terminal, without the Flutter app, to debug chain
+//! specifications and sync issues.
+//!
+//! The chains are started in the order of their specifications, each of them being able to use the
+//! ones before it as its relay chain, under the file name of their specification without its
+//! extension. Once started, the sync status of every chain is printed periodically, and the lines
+//! read from the standard input are sent to the selected chain as JSON-RPC calls.
+
+use flutter_rust_bridge::RustOpaque;
+use smoldot_flutter::api::*;
+use smoldot_flutter::blocking::json_rpc_call;
+use smoldot_flutter::error::SmoldotFlutterError;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::Duration,
+};
+
+const USAGE: &str = "\
+Usage: smoldot-flutter-cli [OPTIONS] <CHAIN_SPEC>...
+
+Starts syncing the chains of the given specifications, for example the ones of
+`assets/chainspecs/`. Parachains must come after their relay chain.
+
+Options:
+  --database <CHAIN>=<FILE>  Starts the chain from the database saved in FILE
+  --status-interval <SECS>   Prints the sync status every SECS seconds, or never
+                             if 0 [default: 10]
+  --server <PORT>            Starts the JSON-RPC server on PORT, or on a port
+                             picked by the OS if 0
+  --allow-origin <ORIGIN>    Accepts the server connections of the web pages
+                             from ORIGIN, such as https://polkadot.js.org
+  -h, --help                 Prints this help
+
+Commands:
+  <METHOD> [PARAMS]          Calls METHOD on the selected chain, with PARAMS as
+                             a JSON array or object [default: []]
+  .chains                    Lists the chains, marking the selected one
+  .use <CHAIN>               Selects the chain to send calls to
+  .status                    Prints the sync status of every chain
+  .save <FILE>               Saves the database of the selected chain to FILE
+  .quit                      Stops every chain and exits
+";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    chain_specs: Vec<PathBuf>,
+    databases: HashMap<String, PathBuf>,
+    status_interval: Duration,
+    server_port: Option<u16>,
+    allowed_origins: Vec<String>,
+}
+
+// Returns `None` if the help was requested.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut parsed = Args {
+        chain_specs: Vec::new(),
+        databases: HashMap::new(),
+        status_interval: Duration::from_secs(10),
+        server_port: None,
+        allowed_origins: Vec::new(),
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("Missing value for '{}'.", arg))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--database" => {
+                let value = value()?;
+                let (chain_name, path) = value
+                    .split_once('=')
+                    .ok_or_else(|| format!("Expected <CHAIN>=<FILE>, got '{}'.", value))?;
+                parsed.databases.insert(chain_name.into(), path.into());
+            }
+            "--status-interval" => {
+                let value = value()?;
+                let secs = value
+                    .parse()
+                    .map_err(|_| format!("Invalid status interval '{}'.", value))?;
+                parsed.status_interval = Duration::from_secs(secs);
+            }
+            "--server" => {
+                let value = value()?;
+                let port = value
+                    .parse()
+                    .map_err(|_| format!("Invalid port '{}'.", value))?;
+                parsed.server_port = Some(port);
+            }
+            "--allow-origin" => parsed.allowed_origins.push(value()?),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'.", arg)),
+            _ => parsed.chain_specs.push(arg.into()),
+        }
+    }
+    if parsed.chain_specs.is_empty() {
+        return Err("No chain specification given.".into());
+    }
+    Ok(Some(parsed))
+}
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return;
+        }
+        Err(err) => {
+            eprint!("{}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    if let Err(err) = run(args) {
+        eprintln!("{:#}", err);
+        process::exit(1);
+    }
+}
+
+fn run(args: Args) -> anyhow::Result<()> {
+    let client = create_light_client(None);
+    let mut chains = Vec::new();
+    for chain_spec in &args.chain_specs {
+        let chain = start(&client, &chains, chain_spec, &args.databases)?;
+        println!("Started chain '{}'.", chain.0);
+        chains.push(chain);
+    }
+
+    if let Some(port) = args.server_port {
+        let port = light_client_start_json_rpc_server(
+            client.clone(),
+            Some(port),
+            args.allowed_origins.clone(),
+        )?;
+        println!(
+            "JSON-RPC server listening on ws://127.0.0.1:{}/<chain>.",
+            port
+        );
+    }
+
+    if !args.status_interval.is_zero() {
+        let chains = chains.clone();
+        thread::spawn(move || loop {
+            thread::sleep(args.status_interval);
+            print_status(&chains);
+        });
+    }
+
+    let mut selected = chains.len() - 1;
+    println!("Sending calls to chain '{}'.", chains[selected].0);
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        let (command, argument) = match line.trim().split_once(char::is_whitespace) {
+            Some((command, argument)) => (command, Some(argument.trim())),
+            None => (line.trim(), None),
+        };
+        match (command, argument) {
+            ("", _) => {}
+            (".quit", _) => break,
+            (".chains", _) => {
+                for (index, (chain_name, _)) in chains.iter().enumerate() {
+                    let marker = if index == selected { '*' } else { ' ' };
+                    println!("{} {}", marker, chain_name);
+                }
+            }
+            (".use", Some(chain_name)) => {
+                match chains.iter().position(|(name, _)| name == chain_name) {
+                    Some(index) => selected = index,
+                    None => println!("Unknown chain '{}'.", chain_name),
+                }
+            }
+            (".status", _) => print_status(&chains),
+            (".save", Some(path)) => match save_database(&chains[selected].1, Path::new(path)) {
+                Ok(()) => println!("Database saved to '{}'.", path),
+                Err(err) => println!("{:#}", err),
+            },
+            (".use" | ".save", None) => println!("Missing argument for '{}'.", command),
+            (method, params) => {
+                match json_rpc_call(&chains[selected].1, method, params.unwrap_or("[]")) {
+                    Ok(result) => println!("{}", result),
+                    Err(err) => println!("{}", err),
+                }
+            }
+        }
+        io::stdout().flush()?;
+    }
+
+    light_client_stop_json_rpc_server(client.clone())?;
+    for (_, chain) in chains.into_iter().rev() {
+        stop_chain_sync(chain, None)?;
+    }
+    light_client_shutdown(client)?;
+    Ok(())
+}
+
+type NamedChain = (String, RustOpaque<ChainHandle>);
+
+// Starts the chain of the given specification, with the chains started before it as candidate
+// relay chains.
+fn start(
+    client: &RustOpaque<LightClient>,
+    chains: &[NamedChain],
+    chain_spec: &Path,
+    databases: &HashMap<String, PathBuf>,
+) -> anyhow::Result<NamedChain> {
+    let chain_name = chain_spec
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Invalid chain specification path {:?}.", chain_spec))?
+        .to_owned();
+    let spec = fs::read_to_string(chain_spec)
+        .map_err(|err| anyhow::anyhow!("Failed to read {:?}: {}", chain_spec, err))?;
+    let database = match databases.get(&chain_name) {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|err| anyhow::anyhow!("Failed to read {:?}: {}", path, err))?,
+        None => String::new(),
+    };
+    let relay_chains = chains.iter().map(|(name, _)| name.clone()).collect();
+
+    let chain = light_client_start_chain_sync(
+        client.clone(),
+        chain_name.clone(),
+        spec,
+        database,
+        relay_chains,
+        None,
+    )?;
+    Ok((chain_name, chain))
+}
+
+fn print_status(chains: &[NamedChain]) {
+    for (chain_name, chain) in chains {
+        match status(chain) {
+            Ok(status) => println!("{}: {}", chain_name, status),
+            Err(err) => println!("{}: {}", chain_name, err),
+        }
+    }
+}
+
+fn status(chain: &RustOpaque<ChainHandle>) -> Result<String, SmoldotFlutterError> {
+    let health = json_rpc_call(chain, "system_health", "[]")?;
+    let header = json_rpc_call(chain, "chain_getHeader", "[]")?;
+    let health: serde_json::Value = serde_json::from_str(&health).unwrap_or_default();
+    let header: serde_json::Value = serde_json::from_str(&header).unwrap_or_default();
+
+    let best_block = header["number"]
+        .as_str()
+        .and_then(|number| u64::from_str_radix(number.trim_start_matches("0x"), 16).ok());
+    Ok(format!(
+        "best block #{}, {} peer(s), {}",
+        best_block.map_or_else(|| "?".into(), |number| number.to_string()),
+        health["peers"],
+        if health["isSyncing"].as_bool().unwrap_or(true) {
+            "syncing"
+        } else {
+            "idle"
+        }
+    ))
+}
+
+fn save_database(chain: &RustOpaque<ChainHandle>, path: &Path) -> anyhow::Result<()> {
+    let database = export_chain_database(chain.clone(), None)?;
+    fs::write(path, database)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_args() {
+        let args = parse(&[
+            "kusama.json",
+            "--database",
+            "kusama=kusama.db",
+            "--status-interval",
+            "0",
+            "statemine.json",
+            "--server",
+            "9944",
+            "--allow-origin",
+            "https://polkadot.js.org",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            args,
+            Args {
+                chain_specs: vec!["kusama.json".into(), "statemine.json".into()],
+                databases: HashMap::from([("kusama".into(), "kusama.db".into())]),
+                status_interval: Duration::ZERO,
+                server_port: Some(9944),
+                allowed_origins: vec!["https://polkadot.js.org".into()],
+            }
+        );
+
+        assert_eq!(parse(&["kusama.json", "--help"]), Ok(None));
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["kusama.json", "--database", "kusama"]).is_err());
+        assert!(parse(&["kusama.json", "--server"]).is_err());
+        assert!(parse(&["kusama.json", "--verbose"]).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! Blocking counterparts of the functions of `api` that answer through a stream, for Rust callers
+//! that can't receive one, such as the command-line client.
+
+use crate::api::{self, ChainHandle};
+use crate::error::SmoldotFlutterError;
+
+/// Sends a JSON-RPC request to the chain and waits for its response, as `api::json_rpc_call`
+/// does, returning the `result` of the response as JSON text.
+pub fn json_rpc_call(
+    chain: &ChainHandle,
+    method: &str,
+    params: &str,
+) -> Result<String, SmoldotFlutterError> {
+    api::call(chain, method, params, None)
+}
@@ -7,8 +7,37 @@ pub extern "C" fn wire_init_logger(port_: i64) {
 }
 
 #[no_mangle]
-pub extern "C" fn wire_init_light_client(port_: i64) {
-    wire_init_light_client_impl(port_)
+pub extern "C" fn wire_init_light_client(port_: i64, config: *mut wire_LightClientConfig) {
+    wire_init_light_client_impl(port_, config)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_create_light_client(port_: i64, config: *mut wire_LightClientConfig) {
+    wire_create_light_client_impl(port_, config)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_shutdown_light_client(port_: i64) {
+    wire_shutdown_light_client_impl(port_)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_light_client_shutdown(port_: i64, client: wire_LightClient) {
+    wire_light_client_shutdown_impl(port_, client)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_throttle_light_client(port_: i64, throttled: bool) {
+    wire_throttle_light_client_impl(port_, throttled)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_light_client_throttle(
+    port_: i64,
+    client: wire_LightClient,
+    throttled: bool,
+) {
+    wire_light_client_throttle_impl(port_, client, throttled)
 }
 
 #[no_mangle]
@@ -17,32 +46,237 @@ pub extern "C" fn wire_start_chain_sync(
     chain_name: *mut wire_uint_8_list,
     chain_spec: *mut wire_uint_8_list,
     database: *mut wire_uint_8_list,
-    relay_chain: *mut wire_uint_8_list,
+    relay_chains: *mut wire_StringList,
+    options: *mut wire_ChainOptions,
 ) {
-    wire_start_chain_sync_impl(port_, chain_name, chain_spec, database, relay_chain)
+    wire_start_chain_sync_impl(
+        port_,
+        chain_name,
+        chain_spec,
+        database,
+        relay_chains,
+        options,
+    )
 }
 
 #[no_mangle]
-pub extern "C" fn wire_stop_chain_sync(port_: i64, chain_name: *mut wire_uint_8_list) {
-    wire_stop_chain_sync_impl(port_, chain_name)
+pub extern "C" fn wire_light_client_start_chain_sync(
+    port_: i64,
+    client: wire_LightClient,
+    chain_name: *mut wire_uint_8_list,
+    chain_spec: *mut wire_uint_8_list,
+    database: *mut wire_uint_8_list,
+    relay_chains: *mut wire_StringList,
+    options: *mut wire_ChainOptions,
+) {
+    wire_light_client_start_chain_sync_impl(
+        port_,
+        client,
+        chain_name,
+        chain_spec,
+        database,
+        relay_chains,
+        options,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn wire_restart_chain_sync(
+    port_: i64,
+    chain: wire_ChainHandle,
+    chain_spec: *mut wire_uint_8_list,
+    database: *mut wire_uint_8_list,
+    relay_chains: *mut wire_StringList,
+    options: *mut wire_ChainOptions,
+) {
+    wire_restart_chain_sync_impl(port_, chain, chain_spec, database, relay_chains, options)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_export_chain_database(
+    port_: i64,
+    chain: wire_ChainHandle,
+    max_size_bytes: *mut u64,
+) {
+    wire_export_chain_database_impl(port_, chain, max_size_bytes)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_import_chain_database(
+    port_: i64,
+    chain: wire_ChainHandle,
+    database: *mut wire_uint_8_list,
+) {
+    wire_import_chain_database_impl(port_, chain, database)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_chain_sync(
+    port_: i64,
+    chain: wire_ChainHandle,
+    save_timeout_millis: *mut u32,
+) {
+    wire_stop_chain_sync_impl(port_, chain, save_timeout_millis)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_suspend_all_chains(port_: i64, save_timeout_millis: u32) {
+    wire_suspend_all_chains_impl(port_, save_timeout_millis)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_light_client_suspend_all_chains(
+    port_: i64,
+    client: wire_LightClient,
+    save_timeout_millis: u32,
+) {
+    wire_light_client_suspend_all_chains_impl(port_, client, save_timeout_millis)
 }
 
 #[no_mangle]
 pub extern "C" fn wire_send_json_rpc_request(
     port_: i64,
-    chain_name: *mut wire_uint_8_list,
+    chain: wire_ChainHandle,
     req: *mut wire_uint_8_list,
 ) {
-    wire_send_json_rpc_request_impl(port_, chain_name, req)
+    wire_send_json_rpc_request_impl(port_, chain, req)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_json_rpc_call(
+    port_: i64,
+    chain: wire_ChainHandle,
+    method: *mut wire_uint_8_list,
+    params: *mut wire_uint_8_list,
+) {
+    wire_json_rpc_call_impl(port_, chain, method, params)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_subscribe(
+    port_: i64,
+    chain: wire_ChainHandle,
+    method: *mut wire_uint_8_list,
+    params: *mut wire_uint_8_list,
+    unsubscribe_method: *mut wire_uint_8_list,
+) {
+    wire_subscribe_impl(port_, chain, method, params, unsubscribe_method)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_listen_json_rpc_responses(
+    port_: i64,
+    chain: wire_ChainHandle,
+    replay: *mut u32,
+) {
+    wire_listen_json_rpc_responses_impl(port_, chain, replay)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_listen_json_rpc_responses(port_: i64, chain_name: *mut wire_uint_8_list) {
-    wire_listen_json_rpc_responses_impl(port_, chain_name)
+pub extern "C" fn wire_ack_json_rpc_responses(port_: i64, chain: wire_ChainHandle, count: u32) {
+    wire_ack_json_rpc_responses_impl(port_, chain, count)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_unlisten_json_rpc_responses(port_: i64, chain: wire_ChainHandle) {
+    wire_unlisten_json_rpc_responses_impl(port_, chain)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_json_rpc_metrics(port_: i64, chain: wire_ChainHandle) {
+    wire_json_rpc_metrics_impl(port_, chain)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_watch_chain_status(port_: i64, chain: wire_ChainHandle) {
+    wire_watch_chain_status_impl(port_, chain)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_start_json_rpc_server(
+    port_: i64,
+    port: *mut u16,
+    allowed_origins: *mut wire_StringList,
+) {
+    wire_start_json_rpc_server_impl(port_, port, allowed_origins)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_light_client_start_json_rpc_server(
+    port_: i64,
+    client: wire_LightClient,
+    port: *mut u16,
+    allowed_origins: *mut wire_StringList,
+) {
+    wire_light_client_start_json_rpc_server_impl(port_, client, port, allowed_origins)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_json_rpc_server(port_: i64) {
+    wire_stop_json_rpc_server_impl(port_)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_light_client_stop_json_rpc_server(port_: i64, client: wire_LightClient) {
+    wire_light_client_stop_json_rpc_server_impl(port_, client)
 }
 
 // Section: allocate functions
 
+#[no_mangle]
+pub extern "C" fn new_ChainHandle() -> wire_ChainHandle {
+    wire_ChainHandle::new_with_null_ptr()
+}
+
+#[no_mangle]
+pub extern "C" fn new_LightClient() -> wire_LightClient {
+    wire_LightClient::new_with_null_ptr()
+}
+
+#[no_mangle]
+pub extern "C" fn new_StringList_0(len: i32) -> *mut wire_StringList {
+    let wrap = wire_StringList {
+        ptr: support::new_leak_vec_ptr(<*mut wire_uint_8_list>::new_with_null_ptr(), len),
+        len,
+    };
+    support::new_leak_box_ptr(wrap)
+}
+
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_bool_0(value: bool) -> *mut bool {
+    support::new_leak_box_ptr(value)
+}
+
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_chain_options_0() -> *mut wire_ChainOptions {
+    support::new_leak_box_ptr(wire_ChainOptions::new_with_null_ptr())
+}
+
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_light_client_config_0() -> *mut wire_LightClientConfig {
+    support::new_leak_box_ptr(wire_LightClientConfig::new_with_null_ptr())
+}
+
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_overflow_policy_0(value: i32) -> *mut i32 {
+    support::new_leak_box_ptr(value)
+}
+
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_u16_0(value: u16) -> *mut u16 {
+    support::new_leak_box_ptr(value)
+}
+
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_u32_0(value: u32) -> *mut u32 {
+    support::new_leak_box_ptr(value)
+}
+
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_u64_0(value: u64) -> *mut u64 {
+    support::new_leak_box_ptr(value)
+}
+
 #[no_mangle]
 pub extern "C" fn new_uint_8_list_0(len: i32) -> *mut wire_uint_8_list {
     let ans = wire_uint_8_list {
@@ -54,15 +288,125 @@ pub extern "C" fn new_uint_8_list_0(len: i32) -> *mut wire_uint_8_list {
 
 // Section: related functions
 
+#[no_mangle]
+pub extern "C" fn drop_opaque_ChainHandle(ptr: *const c_void) {
+    unsafe {
+        Arc::<ChainHandle>::decrement_strong_count(ptr as _);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn share_opaque_ChainHandle(ptr: *const c_void) -> *const c_void {
+    unsafe {
+        Arc::<ChainHandle>::increment_strong_count(ptr as _);
+        ptr
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn drop_opaque_LightClient(ptr: *const c_void) {
+    unsafe {
+        Arc::<LightClient>::decrement_strong_count(ptr as _);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn share_opaque_LightClient(ptr: *const c_void) -> *const c_void {
+    unsafe {
+        Arc::<LightClient>::increment_strong_count(ptr as _);
+        ptr
+    }
+}
+
 // Section: impl Wire2Api
 
+impl Wire2Api<RustOpaque<ChainHandle>> for wire_ChainHandle {
+    fn wire2api(self) -> RustOpaque<ChainHandle> {
+        unsafe { support::opaque_from_dart(self.ptr as _) }
+    }
+}
+impl Wire2Api<RustOpaque<LightClient>> for wire_LightClient {
+    fn wire2api(self) -> RustOpaque<LightClient> {
+        unsafe { support::opaque_from_dart(self.ptr as _) }
+    }
+}
 impl Wire2Api<String> for *mut wire_uint_8_list {
     fn wire2api(self) -> String {
         let vec: Vec<u8> = self.wire2api();
         String::from_utf8_lossy(&vec).into_owned()
     }
 }
+impl Wire2Api<Vec<String>> for *mut wire_StringList {
+    fn wire2api(self) -> Vec<String> {
+        let vec = unsafe {
+            let wrap = support::box_from_leak_ptr(self);
+            support::vec_from_leak_ptr(wrap.ptr, wrap.len)
+        };
+        vec.into_iter().map(Wire2Api::wire2api).collect()
+    }
+}
 
+impl Wire2Api<bool> for *mut bool {
+    fn wire2api(self) -> bool {
+        unsafe { *support::box_from_leak_ptr(self) }
+    }
+}
+impl Wire2Api<ChainOptions> for *mut wire_ChainOptions {
+    fn wire2api(self) -> ChainOptions {
+        let wrap = unsafe { support::box_from_leak_ptr(self) };
+        Wire2Api::<ChainOptions>::wire2api(*wrap).into()
+    }
+}
+impl Wire2Api<LightClientConfig> for *mut wire_LightClientConfig {
+    fn wire2api(self) -> LightClientConfig {
+        let wrap = unsafe { support::box_from_leak_ptr(self) };
+        Wire2Api::<LightClientConfig>::wire2api(*wrap).into()
+    }
+}
+impl Wire2Api<OverflowPolicy> for *mut i32 {
+    fn wire2api(self) -> OverflowPolicy {
+        let wrap = unsafe { support::box_from_leak_ptr(self) };
+        Wire2Api::<OverflowPolicy>::wire2api(*wrap).into()
+    }
+}
+impl Wire2Api<u16> for *mut u16 {
+    fn wire2api(self) -> u16 {
+        unsafe { *support::box_from_leak_ptr(self) }
+    }
+}
+impl Wire2Api<u32> for *mut u32 {
+    fn wire2api(self) -> u32 {
+        unsafe { *support::box_from_leak_ptr(self) }
+    }
+}
+impl Wire2Api<u64> for *mut u64 {
+    fn wire2api(self) -> u64 {
+        unsafe { *support::box_from_leak_ptr(self) }
+    }
+}
+impl Wire2Api<ChainOptions> for wire_ChainOptions {
+    fn wire2api(self) -> ChainOptions {
+        ChainOptions {
+            max_pending_requests: self.max_pending_requests.wire2api(),
+            max_subscriptions: self.max_subscriptions.wire2api(),
+            disable_json_rpc: self.disable_json_rpc.wire2api(),
+            listener_buffer_size: self.listener_buffer_size.wire2api(),
+            overflow_policy: self.overflow_policy.wire2api(),
+        }
+    }
+}
+impl Wire2Api<LightClientConfig> for wire_LightClientConfig {
+    fn wire2api(self) -> LightClientConfig {
+        LightClientConfig {
+            client_name: self.client_name.wire2api(),
+            client_version: self.client_version.wire2api(),
+            allow_tcp: self.allow_tcp.wire2api(),
+            allow_websocket: self.allow_websocket.wire2api(),
+            database_dir: self.database_dir.wire2api(),
+            database_snapshot_interval_secs: self.database_snapshot_interval_secs.wire2api(),
+        }
+    }
+}
 impl Wire2Api<Vec<u8>> for *mut wire_uint_8_list {
     fn wire2api(self) -> Vec<u8> {
         unsafe {
@@ -73,6 +417,46 @@ impl Wire2Api<Vec<u8>> for *mut wire_uint_8_list {
 }
 // Section: wire structs
 
+#[repr(C)]
+#[derive(Clone)]
+pub struct wire_ChainHandle {
+    ptr: *const core::ffi::c_void,
+}
+
+#[repr(C)]
+#[derive(Clone)]
+pub struct wire_ChainOptions {
+    max_pending_requests: *mut u32,
+    max_subscriptions: *mut u32,
+    disable_json_rpc: *mut bool,
+    listener_buffer_size: *mut u32,
+    overflow_policy: *mut i32,
+}
+
+#[repr(C)]
+#[derive(Clone)]
+pub struct wire_LightClient {
+    ptr: *const core::ffi::c_void,
+}
+
+#[repr(C)]
+#[derive(Clone)]
+pub struct wire_LightClientConfig {
+    client_name: *mut wire_uint_8_list,
+    client_version: *mut wire_uint_8_list,
+    allow_tcp: *mut bool,
+    allow_websocket: *mut bool,
+    database_dir: *mut wire_uint_8_list,
+    database_snapshot_interval_secs: *mut u32,
+}
+
+#[repr(C)]
+#[derive(Clone)]
+pub struct wire_StringList {
+    ptr: *mut *mut wire_uint_8_list,
+    len: i32,
+}
+
 #[repr(C)]
 #[derive(Clone)]
 pub struct wire_uint_8_list {
@@ -92,6 +476,58 @@ impl<T> NewWithNullPtr for *mut T {
     }
 }
 
+impl NewWithNullPtr for wire_ChainHandle {
+    fn new_with_null_ptr() -> Self {
+        Self {
+            ptr: core::ptr::null(),
+        }
+    }
+}
+impl NewWithNullPtr for wire_ChainOptions {
+    fn new_with_null_ptr() -> Self {
+        Self {
+            max_pending_requests: core::ptr::null_mut(),
+            max_subscriptions: core::ptr::null_mut(),
+            disable_json_rpc: core::ptr::null_mut(),
+            listener_buffer_size: core::ptr::null_mut(),
+            overflow_policy: core::ptr::null_mut(),
+        }
+    }
+}
+
+impl Default for wire_ChainOptions {
+    fn default() -> Self {
+        Self::new_with_null_ptr()
+    }
+}
+
+impl NewWithNullPtr for wire_LightClient {
+    fn new_with_null_ptr() -> Self {
+        Self {
+            ptr: core::ptr::null(),
+        }
+    }
+}
+
+impl NewWithNullPtr for wire_LightClientConfig {
+    fn new_with_null_ptr() -> Self {
+        Self {
+            client_name: core::ptr::null_mut(),
+            client_version: core::ptr::null_mut(),
+            allow_tcp: core::ptr::null_mut(),
+            allow_websocket: core::ptr::null_mut(),
+            database_dir: core::ptr::null_mut(),
+            database_snapshot_interval_secs: core::ptr::null_mut(),
+        }
+    }
+}
+
+impl Default for wire_LightClientConfig {
+    fn default() -> Self {
+        Self::new_with_null_ptr()
+    }
+}
+
 // Section: sync execution mode utility
 
 #[no_mangle]
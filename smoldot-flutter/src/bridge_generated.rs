@@ -19,6 +19,15 @@ use std::sync::Arc;
 
 // Section: imports
 
+use crate::broadcaster::OverflowPolicy;
+use crate::database::DatabaseImport;
+use crate::error::SmoldotFlutterError;
+use crate::error::SmoldotFlutterErrorKind;
+use crate::json_rpc::SubscriptionEvent;
+use crate::status::ChainStatus;
+use crate::status::ChainStatusEvent;
+use crate::status::WarpSyncPhase;
+
 // Section: wire functions
 
 fn wire_init_logger_impl(port_: MessagePort) {
@@ -31,14 +40,96 @@ fn wire_init_logger_impl(port_: MessagePort) {
         move || move |task_callback| init_logger(task_callback.stream_sink()),
     )
 }
-fn wire_init_light_client_impl(port_: MessagePort) {
+fn wire_init_light_client_impl(
+    port_: MessagePort,
+    config: impl Wire2Api<Option<LightClientConfig>> + UnwindSafe,
+) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap(
         WrapInfo {
             debug_name: "init_light_client",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
-        move || move |task_callback| init_light_client(),
+        move || {
+            let api_config = config.wire2api();
+            move |task_callback| init_light_client(api_config)
+        },
+    )
+}
+fn wire_create_light_client_impl(
+    port_: MessagePort,
+    config: impl Wire2Api<Option<LightClientConfig>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "create_light_client",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_config = config.wire2api();
+            move |task_callback| Ok(create_light_client(api_config))
+        },
+    )
+}
+fn wire_shutdown_light_client_impl(port_: MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "shutdown_light_client",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || move |task_callback| shutdown_light_client(),
+    )
+}
+fn wire_light_client_shutdown_impl(
+    port_: MessagePort,
+    client: impl Wire2Api<RustOpaque<LightClient>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "light_client_shutdown",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_client = client.wire2api();
+            move |task_callback| light_client_shutdown(api_client)
+        },
+    )
+}
+fn wire_throttle_light_client_impl(
+    port_: MessagePort,
+    throttled: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "throttle_light_client",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_throttled = throttled.wire2api();
+            move |task_callback| throttle_light_client(api_throttled)
+        },
+    )
+}
+fn wire_light_client_throttle_impl(
+    port_: MessagePort,
+    client: impl Wire2Api<RustOpaque<LightClient>> + UnwindSafe,
+    throttled: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "light_client_throttle",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_client = client.wire2api();
+            let api_throttled = throttled.wire2api();
+            move |task_callback| light_client_throttle(api_client, api_throttled)
+        },
     )
 }
 fn wire_start_chain_sync_impl(
@@ -46,7 +137,8 @@ fn wire_start_chain_sync_impl(
     chain_name: impl Wire2Api<String> + UnwindSafe,
     chain_spec: impl Wire2Api<String> + UnwindSafe,
     database: impl Wire2Api<String> + UnwindSafe,
-    relay_chain: impl Wire2Api<Option<String>> + UnwindSafe,
+    relay_chains: impl Wire2Api<Vec<String>> + UnwindSafe,
+    options: impl Wire2Api<Option<ChainOptions>> + UnwindSafe,
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap(
         WrapInfo {
@@ -58,34 +150,180 @@ fn wire_start_chain_sync_impl(
             let api_chain_name = chain_name.wire2api();
             let api_chain_spec = chain_spec.wire2api();
             let api_database = database.wire2api();
-            let api_relay_chain = relay_chain.wire2api();
+            let api_relay_chains = relay_chains.wire2api();
+            let api_options = options.wire2api();
             move |task_callback| {
                 start_chain_sync(
                     api_chain_name,
                     api_chain_spec,
                     api_database,
-                    api_relay_chain,
+                    api_relay_chains,
+                    api_options,
                 )
             }
         },
     )
 }
-fn wire_stop_chain_sync_impl(port_: MessagePort, chain_name: impl Wire2Api<String> + UnwindSafe) {
+fn wire_light_client_start_chain_sync_impl(
+    port_: MessagePort,
+    client: impl Wire2Api<RustOpaque<LightClient>> + UnwindSafe,
+    chain_name: impl Wire2Api<String> + UnwindSafe,
+    chain_spec: impl Wire2Api<String> + UnwindSafe,
+    database: impl Wire2Api<String> + UnwindSafe,
+    relay_chains: impl Wire2Api<Vec<String>> + UnwindSafe,
+    options: impl Wire2Api<Option<ChainOptions>> + UnwindSafe,
+) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap(
         WrapInfo {
-            debug_name: "stop_chain_sync",
+            debug_name: "light_client_start_chain_sync",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
         move || {
+            let api_client = client.wire2api();
             let api_chain_name = chain_name.wire2api();
-            move |task_callback| stop_chain_sync(api_chain_name)
+            let api_chain_spec = chain_spec.wire2api();
+            let api_database = database.wire2api();
+            let api_relay_chains = relay_chains.wire2api();
+            let api_options = options.wire2api();
+            move |task_callback| {
+                light_client_start_chain_sync(
+                    api_client,
+                    api_chain_name,
+                    api_chain_spec,
+                    api_database,
+                    api_relay_chains,
+                    api_options,
+                )
+            }
+        },
+    )
+}
+fn wire_restart_chain_sync_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    chain_spec: impl Wire2Api<String> + UnwindSafe,
+    database: impl Wire2Api<String> + UnwindSafe,
+    relay_chains: impl Wire2Api<Vec<String>> + UnwindSafe,
+    options: impl Wire2Api<Option<ChainOptions>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "restart_chain_sync",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            let api_chain_spec = chain_spec.wire2api();
+            let api_database = database.wire2api();
+            let api_relay_chains = relay_chains.wire2api();
+            let api_options = options.wire2api();
+            move |task_callback| {
+                restart_chain_sync(
+                    api_chain,
+                    api_chain_spec,
+                    api_database,
+                    api_relay_chains,
+                    api_options,
+                )
+            }
+        },
+    )
+}
+fn wire_export_chain_database_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    max_size_bytes: impl Wire2Api<Option<u64>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "export_chain_database",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            let api_max_size_bytes = max_size_bytes.wire2api();
+            move |task_callback| export_chain_database(api_chain, api_max_size_bytes)
+        },
+    )
+}
+fn wire_import_chain_database_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    database: impl Wire2Api<String> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "import_chain_database",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            let api_database = database.wire2api();
+            move |task_callback| import_chain_database(api_chain, api_database)
+        },
+    )
+}
+fn wire_stop_chain_sync_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    save_timeout_millis: impl Wire2Api<Option<u32>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "stop_chain_sync",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            let api_save_timeout_millis = save_timeout_millis.wire2api();
+            move |task_callback| stop_chain_sync(api_chain, api_save_timeout_millis)
+        },
+    )
+}
+fn wire_suspend_all_chains_impl(
+    port_: MessagePort,
+    save_timeout_millis: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "suspend_all_chains",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_save_timeout_millis = save_timeout_millis.wire2api();
+            move |task_callback| suspend_all_chains(api_save_timeout_millis)
+        },
+    )
+}
+fn wire_light_client_suspend_all_chains_impl(
+    port_: MessagePort,
+    client: impl Wire2Api<RustOpaque<LightClient>> + UnwindSafe,
+    save_timeout_millis: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "light_client_suspend_all_chains",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_client = client.wire2api();
+            let api_save_timeout_millis = save_timeout_millis.wire2api();
+            move |task_callback| {
+                light_client_suspend_all_chains(api_client, api_save_timeout_millis)
+            }
         },
     )
 }
 fn wire_send_json_rpc_request_impl(
     port_: MessagePort,
-    chain_name: impl Wire2Api<String> + UnwindSafe,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
     req: impl Wire2Api<String> + UnwindSafe,
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap(
@@ -95,15 +333,73 @@ fn wire_send_json_rpc_request_impl(
             mode: FfiCallMode::Normal,
         },
         move || {
-            let api_chain_name = chain_name.wire2api();
+            let api_chain = chain.wire2api();
             let api_req = req.wire2api();
-            move |task_callback| send_json_rpc_request(api_chain_name, api_req)
+            move |task_callback| send_json_rpc_request(api_chain, api_req)
+        },
+    )
+}
+fn wire_json_rpc_call_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    method: impl Wire2Api<String> + UnwindSafe,
+    params: impl Wire2Api<String> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "json_rpc_call",
+            port: Some(port_),
+            mode: FfiCallMode::Stream,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            let api_method = method.wire2api();
+            let api_params = params.wire2api();
+            move |task_callback| {
+                json_rpc_call(
+                    api_chain,
+                    api_method,
+                    api_params,
+                    task_callback.stream_sink(),
+                )
+            }
+        },
+    )
+}
+fn wire_subscribe_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    method: impl Wire2Api<String> + UnwindSafe,
+    params: impl Wire2Api<String> + UnwindSafe,
+    unsubscribe_method: impl Wire2Api<String> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "subscribe",
+            port: Some(port_),
+            mode: FfiCallMode::Stream,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            let api_method = method.wire2api();
+            let api_params = params.wire2api();
+            let api_unsubscribe_method = unsubscribe_method.wire2api();
+            move |task_callback| {
+                subscribe(
+                    api_chain,
+                    api_method,
+                    api_params,
+                    api_unsubscribe_method,
+                    task_callback.stream_sink(),
+                )
+            }
         },
     )
 }
 fn wire_listen_json_rpc_responses_impl(
     port_: MessagePort,
-    chain_name: impl Wire2Api<String> + UnwindSafe,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    replay: impl Wire2Api<Option<u32>> + UnwindSafe,
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap(
         WrapInfo {
@@ -112,13 +408,146 @@ fn wire_listen_json_rpc_responses_impl(
             mode: FfiCallMode::Stream,
         },
         move || {
-            let api_chain_name = chain_name.wire2api();
+            let api_chain = chain.wire2api();
+            let api_replay = replay.wire2api();
+            move |task_callback| {
+                listen_json_rpc_responses(api_chain, api_replay, task_callback.stream_sink())
+            }
+        },
+    )
+}
+fn wire_ack_json_rpc_responses_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+    count: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "ack_json_rpc_responses",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            let api_count = count.wire2api();
+            move |task_callback| ack_json_rpc_responses(api_chain, api_count)
+        },
+    )
+}
+fn wire_unlisten_json_rpc_responses_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "unlisten_json_rpc_responses",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            move |task_callback| unlisten_json_rpc_responses(api_chain)
+        },
+    )
+}
+fn wire_json_rpc_metrics_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "json_rpc_metrics",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            move |task_callback| json_rpc_metrics(api_chain)
+        },
+    )
+}
+fn wire_watch_chain_status_impl(
+    port_: MessagePort,
+    chain: impl Wire2Api<RustOpaque<ChainHandle>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "watch_chain_status",
+            port: Some(port_),
+            mode: FfiCallMode::Stream,
+        },
+        move || {
+            let api_chain = chain.wire2api();
+            move |task_callback| watch_chain_status(api_chain, task_callback.stream_sink())
+        },
+    )
+}
+fn wire_start_json_rpc_server_impl(
+    port_: MessagePort,
+    port: impl Wire2Api<Option<u16>> + UnwindSafe,
+    allowed_origins: impl Wire2Api<Vec<String>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "start_json_rpc_server",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_port = port.wire2api();
+            let api_allowed_origins = allowed_origins.wire2api();
+            move |task_callback| start_json_rpc_server(api_port, api_allowed_origins)
+        },
+    )
+}
+fn wire_light_client_start_json_rpc_server_impl(
+    port_: MessagePort,
+    client: impl Wire2Api<RustOpaque<LightClient>> + UnwindSafe,
+    port: impl Wire2Api<Option<u16>> + UnwindSafe,
+    allowed_origins: impl Wire2Api<Vec<String>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "light_client_start_json_rpc_server",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_client = client.wire2api();
+            let api_port = port.wire2api();
+            let api_allowed_origins = allowed_origins.wire2api();
             move |task_callback| {
-                listen_json_rpc_responses(api_chain_name, task_callback.stream_sink())
+                light_client_start_json_rpc_server(api_client, api_port, api_allowed_origins)
             }
         },
     )
 }
+fn wire_stop_json_rpc_server_impl(port_: MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "stop_json_rpc_server",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || move |task_callback| stop_json_rpc_server(),
+    )
+}
+fn wire_light_client_stop_json_rpc_server_impl(
+    port_: MessagePort,
+    client: impl Wire2Api<RustOpaque<LightClient>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap(
+        WrapInfo {
+            debug_name: "light_client_stop_json_rpc_server",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_client = client.wire2api();
+            move |task_callback| light_client_stop_json_rpc_server(api_client)
+        },
+    )
+}
 // Section: wrapper structs
 
 // Section: static checks
@@ -142,6 +571,44 @@ where
     }
 }
 
+impl Wire2Api<bool> for bool {
+    fn wire2api(self) -> bool {
+        self
+    }
+}
+
+impl Wire2Api<i32> for i32 {
+    fn wire2api(self) -> i32 {
+        self
+    }
+}
+
+impl Wire2Api<OverflowPolicy> for i32 {
+    fn wire2api(self) -> OverflowPolicy {
+        match self {
+            0 => OverflowPolicy::Block,
+            1 => OverflowPolicy::DropOldest,
+            2 => OverflowPolicy::DropNotifications,
+            _ => unreachable!("Invalid variant for OverflowPolicy: {}", self),
+        }
+    }
+}
+
+impl Wire2Api<u16> for u16 {
+    fn wire2api(self) -> u16 {
+        self
+    }
+}
+impl Wire2Api<u32> for u32 {
+    fn wire2api(self) -> u32 {
+        self
+    }
+}
+impl Wire2Api<u64> for u64 {
+    fn wire2api(self) -> u64 {
+        self
+    }
+}
 impl Wire2Api<u8> for u8 {
     fn wire2api(self) -> u8 {
         self
@@ -150,6 +617,66 @@ impl Wire2Api<u8> for u8 {
 
 // Section: impl IntoDart
 
+impl support::IntoDart for ChainStatus {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.best_block_number.into_dart(),
+            self.best_block_hash.into_dart(),
+            self.finalized_block_number.into_dart(),
+            self.finalized_block_hash.into_dart(),
+            self.peers.into_dart(),
+            self.is_syncing.into_dart(),
+            self.warp_sync_phase.into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for ChainStatus {}
+
+impl support::IntoDart for ChainStatusEvent {
+    fn into_dart(self) -> support::DartAbi {
+        match self {
+            Self::Status(field0) => vec![0.into_dart(), field0.into_dart()],
+            Self::Err(field0) => vec![1.into_dart(), field0.into_dart()],
+        }
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for ChainStatusEvent {}
+
+impl support::IntoDart for DatabaseImport {
+    fn into_dart(self) -> support::DartAbi {
+        match self {
+            Self::Accepted => 0,
+            Self::Malformed => 1,
+            Self::WrongChain => 2,
+            Self::Outdated => 3,
+        }
+        .into_dart()
+    }
+}
+impl support::IntoDart for JsonRpcCallResult {
+    fn into_dart(self) -> support::DartAbi {
+        match self {
+            Self::Ok(field0) => vec![0.into_dart(), field0.into_dart()],
+            Self::Err(field0) => vec![1.into_dart(), field0.into_dart()],
+        }
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for JsonRpcCallResult {}
+
+impl support::IntoDart for JsonRpcMetrics {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.dropped_messages.into_dart(),
+            self.buffered_messages.into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for JsonRpcMetrics {}
+
 impl support::IntoDart for LogEntry {
     fn into_dart(self) -> support::DartAbi {
         vec![
@@ -163,6 +690,57 @@ impl support::IntoDart for LogEntry {
 }
 impl support::IntoDartExceptPrimitive for LogEntry {}
 
+impl support::IntoDart for SmoldotFlutterError {
+    fn into_dart(self) -> support::DartAbi {
+        vec![self.kind.into_dart(), self.message.into_dart()].into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for SmoldotFlutterError {}
+
+impl support::IntoDart for SmoldotFlutterErrorKind {
+    fn into_dart(self) -> support::DartAbi {
+        match self {
+            Self::NotInitialized => 0,
+            Self::AlreadyInitialized => 1,
+            Self::UnknownChain => 2,
+            Self::DuplicateChain => 3,
+            Self::InvalidChainSpec => 4,
+            Self::RelayChainNotFound => 5,
+            Self::QueueFull => 6,
+            Self::InvalidRequest => 7,
+            Self::JsonRpcDisabled => 8,
+            Self::JsonRpcError => 9,
+            Self::MalformedResponse => 10,
+            Self::Timeout => 11,
+            Self::ChainRemoved => 12,
+            Self::Poisoned => 13,
+            Self::ServerUnavailable => 14,
+        }
+        .into_dart()
+    }
+}
+impl support::IntoDart for SubscriptionEvent {
+    fn into_dart(self) -> support::DartAbi {
+        match self {
+            Self::Notification(field0) => vec![0.into_dart(), field0.into_dart()],
+            Self::Err(field0) => vec![1.into_dart(), field0.into_dart()],
+        }
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for SubscriptionEvent {}
+
+impl support::IntoDart for WarpSyncPhase {
+    fn into_dart(self) -> support::DartAbi {
+        match self {
+            Self::WaitingForPeers => 0,
+            Self::InProgress => 1,
+            Self::Finished => 2,
+        }
+        .into_dart()
+    }
+}
+
 // Section: executor
 
 support::lazy_static! {
@@ -0,0 +1,574 @@
+use async_std::channel::{self, Receiver, Sender};
+use flutter_rust_bridge::StreamSink;
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+// Number of most recent messages kept around to be replayed to listeners attaching late.
+const MAX_REPLAYED_MESSAGES: usize = 256;
+
+/// What happens to a message broadcast to a listener whose buffer is full, which happens when
+/// messages are produced faster than the Dart side acknowledges them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Waits for the listener to catch up, which pauses the processing of every response of the
+    /// chain in the meantime, including the ones awaited by `json_rpc_call`.
+    Block,
+    /// Drops the oldest message buffered for the listener.
+    DropOldest,
+    /// Drops the oldest notification buffered for the listener, or the new message if it is a
+    /// notification itself. Waits for the listener to catch up, as with `Block`, if neither is a
+    /// notification.
+    DropNotifications,
+}
+
+/// How the messages waiting to be forwarded to each listener are buffered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BufferConfig {
+    /// Maximum number of messages buffered for each listener.
+    pub(crate) capacity: usize,
+    pub(crate) policy: OverflowPolicy,
+}
+
+/// Fans out the JSON-RPC responses and notifications of a chain to any number of listeners.
+///
+/// Every listener has its own buffer, drained by a task forwarding its messages to Dart, so that a
+/// listener that doesn't keep up doesn't hold back the others.
+///
+/// `StreamSink::add` never blocks, so a forwarding task only posts as many messages to the Dart
+/// isolate as the capacity of the buffer ahead of their acknowledgement through `acknowledge`. The
+/// following ones wait in the buffer, which fills up if the Dart side doesn't keep up.
+pub(crate) struct Broadcaster {
+    config: BufferConfig,
+    inner: Mutex<Inner>,
+    dropped_messages: AtomicU64,
+}
+
+struct Inner {
+    listeners: Vec<Arc<Listener>>,
+    // Messages broadcast while no listener is attached, which are delivered to the next one to
+    // attach. Bounded like the buffer of a listener, but makes room by dropping the oldest
+    // messages if the overflow policy would wait instead. `None` while a listener is attached.
+    backlog: Option<Buffer>,
+    history: VecDeque<String>,
+}
+
+impl Broadcaster {
+    pub(crate) fn new(config: BufferConfig) -> Self {
+        Broadcaster {
+            config,
+            inner: Mutex::new(Inner {
+                listeners: Vec::new(),
+                backlog: Some(Buffer::new(config.capacity)),
+                history: VecDeque::with_capacity(MAX_REPLAYED_MESSAGES),
+            }),
+            dropped_messages: AtomicU64::new(0),
+        }
+    }
+
+    /// Attaches a new listener, which first receives up to `replay` of the most recent messages.
+    ///
+    /// A listener attaching while no other is attached instead receives the messages broadcast
+    /// since the previous ones were detached, or since the chain was started, up to the capacity of
+    /// its buffer. Replayed messages are buffered even beyond that capacity.
+    pub(crate) fn attach(&self, sink: StreamSink<String>, replay: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        let replayed = match inner.backlog.take() {
+            Some(backlog) => backlog.messages,
+            None => {
+                let skipped = inner.history.len().saturating_sub(replay);
+                inner
+                    .history
+                    .iter()
+                    .skip(skipped)
+                    .map(|message| Buffered::new(message.clone(), self.config.policy))
+                    .collect()
+            }
+        };
+        let listener = Arc::new(Listener::new(self.config.capacity));
+        listener.buffer.lock().unwrap().messages = replayed;
+        listener.wake_forwarder();
+        async_std::task::spawn(forward(listener.clone(), sink));
+        inner.listeners.push(listener);
+    }
+
+    /// Sends a message to every listener, forgetting about the ones whose Dart stream has been
+    /// cancelled. The message is kept for the next listener to attach if none is left.
+    ///
+    /// Only returns once the message is buffered for every listener, which might require waiting
+    /// for some of them to catch up depending on the overflow policy.
+    pub(crate) async fn broadcast(&self, message: String) {
+        let listeners = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner.history.len() == MAX_REPLAYED_MESSAGES {
+                inner.history.pop_front();
+            }
+            inner.history.push_back(message.clone());
+
+            inner
+                .listeners
+                .retain(|listener| !listener.buffer.lock().unwrap().closed);
+            if inner.listeners.is_empty() {
+                let capacity = self.config.capacity;
+                let backlog = inner.backlog.get_or_insert_with(|| Buffer::new(capacity));
+                if backlog.push_or_drop_oldest(
+                    Buffered::new(message, self.config.policy),
+                    self.config.policy,
+                ) {
+                    self.dropped_messages.fetch_add(1, Ordering::Relaxed);
+                }
+                return;
+            }
+            inner.listeners.clone()
+        };
+
+        let message = Buffered::new(message, self.config.policy);
+        for listener in listeners {
+            let mut pending = message.clone();
+            loop {
+                let pushed = listener
+                    .buffer
+                    .lock()
+                    .unwrap()
+                    .push(pending, self.config.policy);
+                match pushed {
+                    Ok(dropped) => {
+                        if dropped {
+                            self.dropped_messages.fetch_add(1, Ordering::Relaxed);
+                        }
+                        listener.wake_forwarder();
+                        break;
+                    }
+                    Err(message) => {
+                        pending = message;
+                        let _ = listener.space.1.recv().await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Acknowledges that the Dart side has processed `count` more of the messages forwarded to each
+    /// listener, letting as many more be forwarded.
+    pub(crate) fn acknowledge(&self, count: usize) {
+        let inner = self.inner.lock().unwrap();
+        for listener in &inner.listeners {
+            let mut buffer = listener.buffer.lock().unwrap();
+            buffer.unacknowledged = buffer.unacknowledged.saturating_sub(count);
+            drop(buffer);
+            listener.wake_forwarder();
+        }
+    }
+
+    /// Closes the stream of every listener, once the messages already buffered for it have been
+    /// forwarded. Returns the number of listeners detached.
+    ///
+    /// The messages broadcast afterwards are kept for the next listener to attach.
+    pub(crate) fn detach_all(&self) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        let detached = inner.listeners.len();
+        for listener in inner.listeners.drain(..) {
+            listener.close();
+        }
+        let capacity = self.config.capacity;
+        inner.backlog.get_or_insert_with(|| Buffer::new(capacity));
+        detached
+    }
+
+    /// Returns the number of messages dropped so far because a listener didn't keep up, counted
+    /// once per listener, or because no listener was attached for too long.
+    pub(crate) fn dropped_messages(&self) -> u64 {
+        self.dropped_messages.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of messages waiting to be forwarded, summed over every listener, or
+    /// kept for the next listener to attach.
+    pub(crate) fn buffered_messages(&self) -> usize {
+        let inner = self.inner.lock().unwrap();
+        let backlog = inner
+            .backlog
+            .as_ref()
+            .map_or(0, |backlog| backlog.messages.len());
+        inner
+            .listeners
+            .iter()
+            .map(|listener| listener.buffer.lock().unwrap().messages.len())
+            .sum::<usize>()
+            + backlog
+    }
+}
+
+// A listener attached through `attach`, whose messages are forwarded to Dart by `forward`.
+struct Listener {
+    buffer: Mutex<Buffer>,
+    // Signalled whenever messages are buffered or the listener is closed.
+    ready: (Sender<()>, Receiver<()>),
+    // Signalled whenever room is made in the buffer or the listener is closed.
+    space: (Sender<()>, Receiver<()>),
+}
+
+impl Listener {
+    fn new(capacity: usize) -> Self {
+        Listener {
+            buffer: Mutex::new(Buffer::new(capacity)),
+            ready: channel::bounded(1),
+            space: channel::bounded(1),
+        }
+    }
+
+    fn close(&self) {
+        self.buffer.lock().unwrap().closed = true;
+        self.wake_forwarder();
+        self.wake_broadcaster();
+    }
+
+    fn wake_forwarder(&self) {
+        let _ = self.ready.0.try_send(());
+    }
+
+    fn wake_broadcaster(&self) {
+        let _ = self.space.0.try_send(());
+    }
+
+    // Takes the next message to forward to Dart, unless as many messages as the buffer holds are
+    // still waiting to be acknowledged. Those left once the listener is closed are forwarded
+    // regardless, since the Dart stream ends right after them.
+    fn next(&self) -> Next {
+        let mut buffer = self.buffer.lock().unwrap();
+        if buffer.unacknowledged >= buffer.capacity && !buffer.closed {
+            return Next::Wait;
+        }
+        match buffer.messages.pop_front() {
+            Some(message) => {
+                buffer.unacknowledged += 1;
+                drop(buffer);
+                self.wake_broadcaster();
+                Next::Forward(message)
+            }
+            None if buffer.closed => Next::Close,
+            None => Next::Wait,
+        }
+    }
+}
+
+// What the forwarding task of a listener does next.
+#[derive(Debug, PartialEq, Eq)]
+enum Next {
+    Forward(Buffered),
+    Wait,
+    Close,
+}
+
+// Forwards the messages buffered for a listener to its Dart stream, until either is closed.
+async fn forward(listener: Arc<Listener>, sink: StreamSink<String>) {
+    loop {
+        match listener.next() {
+            Next::Forward(message) => {
+                if !sink.add(message.message) {
+                    // The Dart side has cancelled its stream, the listener is forgotten about on
+                    // the next broadcast.
+                    listener.close();
+                    return;
+                }
+            }
+            Next::Wait => {
+                let _ = listener.ready.1.recv().await;
+            }
+            Next::Close => break,
+        }
+    }
+    sink.close();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Buffered {
+    message: String,
+    // Only determined if the overflow policy tells notifications apart.
+    notification: bool,
+}
+
+impl Buffered {
+    fn new(message: String, policy: OverflowPolicy) -> Self {
+        let notification = policy == OverflowPolicy::DropNotifications && is_notification(&message);
+        Buffered {
+            message,
+            notification,
+        }
+    }
+}
+
+fn is_notification(message: &str) -> bool {
+    match serde_json::from_str::<serde_json::Value>(message) {
+        Ok(message) => message.get("id").is_none() && message.get("method").is_some(),
+        Err(_) => false,
+    }
+}
+
+// Messages waiting to be forwarded to a listener.
+struct Buffer {
+    messages: VecDeque<Buffered>,
+    capacity: usize,
+    // Number of messages forwarded to Dart that haven't been acknowledged yet.
+    unacknowledged: usize,
+    // Set once the listener is closed, after which new messages are discarded.
+    closed: bool,
+}
+
+impl Buffer {
+    fn new(capacity: usize) -> Self {
+        Buffer {
+            messages: VecDeque::new(),
+            capacity,
+            unacknowledged: 0,
+            closed: false,
+        }
+    }
+
+    // Buffers a message, making room for it according to `policy` if the buffer is full. Returns
+    // whether a message had to be dropped, or gives the message back if it has to wait for the
+    // listener to make room instead.
+    fn push(&mut self, message: Buffered, policy: OverflowPolicy) -> Result<bool, Buffered> {
+        if self.closed {
+            return Ok(false);
+        }
+        if self.messages.len() < self.capacity {
+            self.messages.push_back(message);
+            return Ok(false);
+        }
+
+        match policy {
+            OverflowPolicy::Block => Err(message),
+            OverflowPolicy::DropOldest => {
+                self.messages.pop_front();
+                self.messages.push_back(message);
+                Ok(true)
+            }
+            OverflowPolicy::DropNotifications => {
+                match self
+                    .messages
+                    .iter()
+                    .position(|buffered| buffered.notification)
+                {
+                    Some(position) => {
+                        self.messages.remove(position);
+                        self.messages.push_back(message);
+                        Ok(true)
+                    }
+                    None if message.notification => Ok(true),
+                    None => Err(message),
+                }
+            }
+        }
+    }
+
+    // Buffers a message like `push`, but drops the oldest message instead of giving the new one
+    // back. Returns whether a message had to be dropped.
+    fn push_or_drop_oldest(&mut self, message: Buffered, policy: OverflowPolicy) -> bool {
+        match self.push(message, policy) {
+            Ok(dropped) => dropped,
+            Err(message) => {
+                self.messages.pop_front();
+                self.messages.push_back(message);
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const RESPONSE: &str = r#"{"jsonrpc":"2.0","id":1,"result":"0x00"}"#;
+    const NOTIFICATION: &str =
+        r#"{"jsonrpc":"2.0","method":"chain_newHead","params":{"subscription":"a","result":{}}}"#;
+
+    fn buffered(message: &str) -> Buffered {
+        Buffered::new(message.to_owned(), OverflowPolicy::DropNotifications)
+    }
+
+    fn buffer(capacity: usize, messages: &[&str]) -> Buffer {
+        Buffer {
+            messages: messages.iter().map(|message| buffered(message)).collect(),
+            capacity,
+            unacknowledged: 0,
+            closed: false,
+        }
+    }
+
+    #[test]
+    fn applies_overflow_policy_when_full() {
+        let mut full = buffer(2, &[RESPONSE, NOTIFICATION]);
+        assert_eq!(
+            full.push(buffered(RESPONSE), OverflowPolicy::Block),
+            Err(buffered(RESPONSE))
+        );
+        assert_eq!(full.messages.len(), 2);
+
+        assert_eq!(
+            full.push(buffered("new"), OverflowPolicy::DropOldest),
+            Ok(true)
+        );
+        assert_eq!(full.messages, [buffered(NOTIFICATION), buffered("new")]);
+
+        let mut full = buffer(2, &[NOTIFICATION, RESPONSE]);
+        assert_eq!(
+            full.push(buffered(RESPONSE), OverflowPolicy::DropNotifications),
+            Ok(true)
+        );
+        assert_eq!(full.messages, [buffered(RESPONSE), buffered(RESPONSE)]);
+        assert_eq!(
+            full.push(buffered(NOTIFICATION), OverflowPolicy::DropNotifications),
+            Ok(true)
+        );
+        assert_eq!(full.messages, [buffered(RESPONSE), buffered(RESPONSE)]);
+        assert_eq!(
+            full.push(buffered(RESPONSE), OverflowPolicy::DropNotifications),
+            Err(buffered(RESPONSE))
+        );
+    }
+
+    #[test]
+    fn keeps_messages_once_listeners_are_gone() {
+        let broadcaster = Broadcaster::new(BufferConfig {
+            capacity: 2,
+            policy: OverflowPolicy::DropNotifications,
+        });
+        let attach = || {
+            let listener = Arc::new(Listener::new(2));
+            let mut inner = broadcaster.inner.lock().unwrap();
+            inner.backlog = None;
+            inner.listeners.push(listener.clone());
+            listener
+        };
+        let backlog = || {
+            let inner = broadcaster.inner.lock().unwrap();
+            inner
+                .backlog
+                .as_ref()
+                .map(|backlog| backlog.messages.clone())
+        };
+
+        let listener = attach();
+        async_std::task::block_on(broadcaster.broadcast(RESPONSE.to_owned()));
+        assert_eq!(
+            listener.buffer.lock().unwrap().messages,
+            [buffered(RESPONSE)]
+        );
+        assert_eq!(backlog(), None);
+
+        // The Dart stream of the listener has been cancelled.
+        listener.close();
+        async_std::task::block_on(broadcaster.broadcast(NOTIFICATION.to_owned()));
+        assert_eq!(backlog(), Some(VecDeque::from([buffered(NOTIFICATION)])));
+
+        let listener = attach();
+        assert_eq!(broadcaster.detach_all(), 1);
+        assert!(listener.buffer.lock().unwrap().closed);
+        assert_eq!(backlog(), Some(VecDeque::new()));
+    }
+
+    #[test]
+    fn bounds_messages_kept_while_no_listener_is_attached() {
+        let broadcaster = Broadcaster::new(BufferConfig {
+            capacity: 2,
+            policy: OverflowPolicy::Block,
+        });
+        for message in ["1", "2", "3"] {
+            async_std::task::block_on(broadcaster.broadcast(message.to_owned()));
+        }
+        let inner = broadcaster.inner.lock().unwrap();
+        assert_eq!(
+            inner.backlog.as_ref().unwrap().messages,
+            [buffered("2"), buffered("3")]
+        );
+        drop(inner);
+        assert_eq!(broadcaster.dropped_messages(), 1);
+        assert_eq!(broadcaster.buffered_messages(), 2);
+    }
+
+    #[test]
+    fn holds_messages_back_until_acknowledged() {
+        let broadcaster = Broadcaster::new(BufferConfig {
+            capacity: 2,
+            policy: OverflowPolicy::DropOldest,
+        });
+        let listener = Arc::new(Listener::new(2));
+        {
+            let mut inner = broadcaster.inner.lock().unwrap();
+            inner.backlog = None;
+            inner.listeners.push(listener.clone());
+        }
+        let broadcast = |message: &str| {
+            async_std::task::block_on(broadcaster.broadcast(message.to_owned()));
+        };
+
+        broadcast("1");
+        broadcast("2");
+        assert_eq!(listener.next(), Next::Forward(buffered("1")));
+        assert_eq!(listener.next(), Next::Forward(buffered("2")));
+        // The Dart side hasn't acknowledged the messages forwarded so far
+        for message in ["3", "4", "5"] {
+            broadcast(message);
+        }
+        assert_eq!(listener.next(), Next::Wait);
+        assert_eq!(broadcaster.dropped_messages(), 1);
+        assert_eq!(broadcaster.buffered_messages(), 2);
+
+        broadcaster.acknowledge(1);
+        assert_eq!(listener.next(), Next::Forward(buffered("4")));
+        assert_eq!(listener.next(), Next::Wait);
+
+        // Closing the listener flushes its buffer regardless
+        listener.close();
+        assert_eq!(listener.next(), Next::Forward(buffered("5")));
+        assert_eq!(listener.next(), Next::Close);
+    }
+
+    #[test]
+    fn blocks_until_acknowledged() {
+        let broadcaster = Broadcaster::new(BufferConfig {
+            capacity: 1,
+            policy: OverflowPolicy::Block,
+        });
+        let listener = Arc::new(Listener::new(1));
+        {
+            let mut inner = broadcaster.inner.lock().unwrap();
+            inner.backlog = None;
+            inner.listeners.push(listener.clone());
+        }
+
+        async_std::task::block_on(async {
+            broadcaster.broadcast("1".to_owned()).await;
+            assert_eq!(listener.next(), Next::Forward(buffered("1")));
+            broadcaster.broadcast("2".to_owned()).await;
+            let blocked = async_std::future::timeout(
+                Duration::from_millis(100),
+                broadcaster.broadcast("3".to_owned()),
+            );
+            assert!(blocked.await.is_err());
+
+            broadcaster.acknowledge(1);
+            assert_eq!(listener.next(), Next::Forward(buffered("2")));
+            broadcaster.broadcast("3".to_owned()).await;
+        });
+        assert_eq!(broadcaster.dropped_messages(), 0);
+        assert_eq!(broadcaster.buffered_messages(), 1);
+    }
+
+    #[test]
+    fn discards_messages_once_closed() {
+        let mut closed = buffer(2, &[]);
+        closed.closed = true;
+        assert_eq!(
+            closed.push(buffered(RESPONSE), OverflowPolicy::Block),
+            Ok(false)
+        );
+        assert!(closed.messages.is_empty());
+    }
+}
@@ -0,0 +1,689 @@
+use crate::{
+    error::{SmoldotFlutterError, SmoldotFlutterErrorKind},
+    util::hex,
+};
+use log::{info, warn};
+use serde_json::Value;
+use smoldot::{
+    chain_spec::{ChainSpec, FromGenesisStorageError},
+    database::finalized_serialize,
+    header, trie,
+};
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+// First line of every saved database, followed by the version of the format. Files written in
+// another format are ignored.
+const MAGIC: &str = "smoldot-flutter-database";
+const FORMAT_VERSION: u32 = 2;
+// Version of the databases saved without a header, which are still loaded once checked.
+const LEGACY_FORMAT_VERSION: u32 = 1;
+
+const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+// Set by `build.rs`.
+const SMOLDOT_REVISION: &str = env!("SMOLDOT_REVISION");
+
+static NEXT_TMP_FILE: AtomicU64 = AtomicU64::new(0);
+
+/// What `import_chain_database` made of a database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseImport {
+    /// The chain was restarted from the database.
+    Accepted,
+    /// The database isn't in a format the light client understands.
+    Malformed,
+    /// The database belongs to another chain.
+    WrongChain,
+    /// The database doesn't contain a finalized block more recent than the checkpoint of the chain
+    /// specification, which the chain keeps syncing from.
+    Outdated,
+}
+
+/// A database passed to `import_chain_database`, as far as it can be checked before restarting the
+/// chain from it.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Candidate {
+    /// The light client would ignore the database, for the given reason.
+    Ignored(DatabaseImport),
+    /// The light client should start the chain from the finalized block of the database, which only
+    /// the chain restarted from it can tell for sure.
+    Usable(FinalizedBlock),
+}
+
+/// Checks that the database belongs to the chain of the given specification, whose genesis hash
+/// is `genesis_hash`, and that its finalized block can be decoded.
+pub(crate) fn check(
+    chain_spec: &str,
+    genesis_hash: &str,
+    database: &str,
+) -> Result<Candidate, SmoldotFlutterError> {
+    let chain_spec = parse_chain_spec(chain_spec)?;
+    let block_number_bytes = usize::from(chain_spec.block_number_bytes());
+    let Some(database_genesis_hash) = genesis_hash_field(database) else {
+        return Ok(Candidate::Ignored(DatabaseImport::Malformed));
+    };
+    if !database_genesis_hash.eq_ignore_ascii_case(genesis_hash) {
+        return Ok(Candidate::Ignored(DatabaseImport::WrongChain));
+    }
+    let Ok(number) = finalized_block_number(database, block_number_bytes) else {
+        return Ok(Candidate::Ignored(DatabaseImport::Malformed));
+    };
+    // The database of a parachain doesn't contain its finalized block.
+    let Some(number) = number else {
+        return Ok(Candidate::Ignored(DatabaseImport::Outdated));
+    };
+    let checkpoint_number = chain_spec
+        .light_sync_state()
+        .and_then(|state| state.to_chain_information().ok())
+        .map(|checkpoint| checkpoint.as_ref().finalized_block_header.number);
+    Ok(Candidate::Usable(FinalizedBlock {
+        number,
+        checkpoint_number,
+        block_number_bytes,
+    }))
+}
+
+/// Finalized block of a database that passed `check`.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct FinalizedBlock {
+    number: u64,
+    // Number of the finalized block of the checkpoint of the specification, if it has one.
+    checkpoint_number: Option<u64>,
+    block_number_bytes: usize,
+}
+
+impl FinalizedBlock {
+    /// Tells what the light client made of the database, given the database exported from the
+    /// chain once restarted from it.
+    ///
+    /// The light client accepted the database if the restarted chain is ahead of the checkpoint of
+    /// its specification, or, if the specification has none, if the restarted chain is at the
+    /// finalized block of the database rather than at the genesis block.
+    pub(crate) fn outcome(&self, restarted_database: &str) -> DatabaseImport {
+        let restarted = finalized_block_number(restarted_database, self.block_number_bytes)
+            .ok()
+            .flatten();
+        let accepted = match self.checkpoint_number {
+            Some(checkpoint_number) => restarted.is_some_and(|number| number > checkpoint_number),
+            None => restarted == Some(self.number),
+        };
+        if accepted {
+            DatabaseImport::Accepted
+        } else if self
+            .checkpoint_number
+            .is_some_and(|checkpoint_number| self.number <= checkpoint_number)
+        {
+            DatabaseImport::Outdated
+        } else {
+            // The light client rejected the database for another reason
+            DatabaseImport::Malformed
+        }
+    }
+}
+
+/// Returns the genesis hash of the chain of a specification, which is costly to compute as this
+/// involves compiling and running the runtime of the genesis block. It is therefore only computed
+/// once for each `cache`.
+pub(crate) fn genesis_hash<'a>(
+    chain_spec: &str,
+    cache: &'a OnceLock<String>,
+) -> Result<&'a str, SmoldotFlutterError> {
+    if let Some(genesis_hash) = cache.get() {
+        return Ok(genesis_hash);
+    }
+    let genesis_hash = hex(&genesis_hash_of(&parse_chain_spec(chain_spec)?)?);
+    Ok(cache.get_or_init(|| genesis_hash))
+}
+
+// Returns the `genesisHash` field of the database, if it is JSON and has one.
+fn genesis_hash_field(database: &str) -> Option<String> {
+    let database = serde_json::from_str::<Value>(database).ok()?;
+    database["genesisHash"].as_str().map(str::to_owned)
+}
+
+// Returns the number of the finalized block of the database, or `None` if it has none, as is the
+// case of the databases of parachains. Fails if the database isn't JSON or if smoldot can't decode
+// its finalized block.
+fn finalized_block_number(database: &str, block_number_bytes: usize) -> Result<Option<u64>, ()> {
+    let Ok(Value::Object(mut database)) = serde_json::from_str::<Value>(database) else {
+        return Err(());
+    };
+    match database.remove("chain") {
+        None | Some(Value::Null) => Ok(None),
+        Some(chain) => finalized_serialize::decode_chain(&chain.to_string(), block_number_bytes)
+            .map(|decoded| {
+                Some(
+                    decoded
+                        .chain_information
+                        .as_ref()
+                        .finalized_block_header
+                        .number,
+                )
+            })
+            .map_err(|_| ()),
+    }
+}
+
+fn parse_chain_spec(chain_spec: &str) -> Result<ChainSpec, SmoldotFlutterError> {
+    ChainSpec::from_json_bytes(chain_spec).map_err(|err| {
+        SmoldotFlutterError::new(SmoldotFlutterErrorKind::InvalidChainSpec, err.to_string())
+    })
+}
+
+fn genesis_hash_of(chain_spec: &ChainSpec) -> Result<[u8; 32], SmoldotFlutterError> {
+    let block_number_bytes = usize::from(chain_spec.block_number_bytes());
+    let genesis_header = match chain_spec.to_chain_information() {
+        Ok((chain_information, _)) => chain_information.as_ref().finalized_block_header.into(),
+        // The specification only contains the root of the genesis storage, from which the genesis
+        // header can be built nonetheless.
+        Err(FromGenesisStorageError::UnknownStorageItems) => header::Header {
+            parent_hash: [0; 32],
+            number: 0,
+            state_root: *chain_spec
+                .genesis_storage()
+                .into_trie_root_hash()
+                .expect("storage items are unknown"),
+            extrinsics_root: trie::EMPTY_BLAKE2_TRIE_MERKLE_VALUE,
+            digest: header::DigestRef::empty().into(),
+        },
+        Err(err) => {
+            return Err(SmoldotFlutterError::new(
+                SmoldotFlutterErrorKind::InvalidChainSpec,
+                err.to_string(),
+            ))
+        }
+    };
+    Ok(genesis_header.hash(block_number_bytes))
+}
+
+// CRC-32 of the bytes, as computed by zlib.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+// Describes the database following it in a saved file, after a blank line:
+//
+// ```text
+// smoldot-flutter-database 2
+// crate-version: 0.1.0
+// smoldot-revision: <commit or version of smoldot>
+// genesis-hash: <hex>
+// saved-at: <seconds since the UNIX epoch>
+// checksum: <CRC-32 of the database, in hex>
+// ```
+struct Header {
+    crate_version: String,
+    smoldot_revision: String,
+    genesis_hash: String,
+    saved_at: u64,
+    checksum: u32,
+}
+
+impl Header {
+    fn new(database: &str) -> io::Result<Self> {
+        let genesis_hash = genesis_hash_field(database)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no genesis hash"))?;
+        Ok(Header {
+            crate_version: CRATE_VERSION.into(),
+            smoldot_revision: SMOLDOT_REVISION.into(),
+            genesis_hash,
+            saved_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| since_epoch.as_secs()),
+            checksum: crc32(database.as_bytes()),
+        })
+    }
+
+    fn parse(header: &str) -> Option<Self> {
+        let mut fields: HashMap<&str, &str> = header
+            .lines()
+            .filter_map(|line| line.split_once(": "))
+            .collect();
+        Some(Header {
+            crate_version: fields.remove("crate-version")?.into(),
+            smoldot_revision: fields.remove("smoldot-revision")?.into(),
+            genesis_hash: fields.remove("genesis-hash")?.into(),
+            saved_at: fields.remove("saved-at")?.parse().ok()?,
+            checksum: u32::from_str_radix(fields.remove("checksum")?, 16).ok()?,
+        })
+    }
+
+    fn write(&self, mut to: impl io::Write) -> io::Result<()> {
+        writeln!(to, "{} {}", MAGIC, FORMAT_VERSION)?;
+        writeln!(to, "crate-version: {}", self.crate_version)?;
+        writeln!(to, "smoldot-revision: {}", self.smoldot_revision)?;
+        writeln!(to, "genesis-hash: {}", self.genesis_hash)?;
+        writeln!(to, "saved-at: {}", self.saved_at)?;
+        writeln!(to, "checksum: {:08x}", self.checksum)?;
+        writeln!(to)
+    }
+}
+
+/// Directory in which the finalized database of each chain is saved, so that the chain can start
+/// syncing from where it left off the next time it is started.
+///
+/// The database of a chain is saved to `<chain_name>.db`, with the characters of the name that
+/// aren't ASCII letters, digits, `-` or `_` percent-encoded.
+#[derive(Debug, Clone)]
+pub(crate) struct DatabaseStore {
+    dir: PathBuf,
+    snapshot_interval: Duration,
+}
+
+impl DatabaseStore {
+    pub(crate) fn new(dir: impl Into<PathBuf>, snapshot_interval: Duration) -> Self {
+        DatabaseStore {
+            dir: dir.into(),
+            snapshot_interval,
+        }
+    }
+
+    /// Returns how often the database of each chain is saved.
+    pub(crate) fn snapshot_interval(&self) -> Duration {
+        self.snapshot_interval
+    }
+
+    /// Returns the database last saved for the chain of the given specification, or `None` if
+    /// there is none or if it can't be used, in which case the chain starts from the checkpoint of
+    /// its specification.
+    ///
+    /// A corrupted database is ignored, as is one that belongs to another chain, which requires
+    /// the genesis hash of the chain, cached in `genesis_hash`. One saved by another revision of
+    /// smoldot, or before databases had a header, is only returned if it can still be decoded.
+    pub(crate) fn load(
+        &self,
+        chain_name: &str,
+        chain_spec: &str,
+        genesis_hash: &OnceLock<String>,
+    ) -> Option<String> {
+        let path = self.path(chain_name);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("Failed to read database {:?}: {}", path, err);
+                return None;
+            }
+        };
+        let (first_line, rest) = content.split_once('\n').unwrap_or((&content, ""));
+        let version = first_line
+            .strip_prefix(MAGIC)
+            .and_then(|version| version.strip_prefix(' '))
+            .and_then(|version| version.parse().ok());
+        let (header, database) = match version {
+            Some(FORMAT_VERSION) => {
+                let header = rest
+                    .split_once("\n\n")
+                    .and_then(|(header, database)| Some((Header::parse(header)?, database)));
+                let Some((header, database)) = header else {
+                    warn!("Ignoring database {:?} with malformed header.", path);
+                    return None;
+                };
+                (Some(header), database)
+            }
+            Some(LEGACY_FORMAT_VERSION) => (None, rest),
+            _ => {
+                warn!("Ignoring database {:?} of unknown format.", path);
+                return None;
+            }
+        };
+        verify(&path, chain_spec, genesis_hash, header.as_ref(), database)
+            .then(|| database.to_owned())
+    }
+
+    /// Saves the database of the chain, replacing the previous one.
+    ///
+    /// The database is written to a temporary file first, then renamed, so that an interrupted
+    /// write leaves the previous database untouched. Each save uses its own temporary file, as the
+    /// periodic snapshots of a chain can race with the one taken when it is stopped.
+    pub(crate) fn save(&self, chain_name: &str, database: &str) -> io::Result<()> {
+        let header = Header::new(database)?;
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(chain_name);
+        let tmp_path = path.with_extension(format!(
+            "db.{}.tmp",
+            NEXT_TMP_FILE.fetch_add(1, Ordering::Relaxed)
+        ));
+        let saved = fs::File::create(&tmp_path)
+            .and_then(|mut file| {
+                header.write(&mut file)?;
+                file.write_all(database.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|()| fs::rename(&tmp_path, path));
+        if saved.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        saved
+    }
+
+    fn path(&self, chain_name: &str) -> PathBuf {
+        let mut file_name = String::with_capacity(chain_name.len() + 3);
+        for byte in chain_name.bytes() {
+            if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+                file_name.push(byte as char);
+            } else {
+                let _ = write!(file_name, "%{:02X}", byte);
+            }
+        }
+        file_name.push_str(".db");
+        self.dir.join(file_name)
+    }
+}
+
+// Tells whether the database loaded from `path` can be used to start the chain, logging why it
+// can't otherwise.
+fn verify(
+    path: &Path,
+    chain_spec: &str,
+    genesis_hash: &OnceLock<String>,
+    header: Option<&Header>,
+    database: &str,
+) -> bool {
+    if header.is_some_and(|header| crc32(database.as_bytes()) != header.checksum) {
+        warn!("Ignoring corrupted database {:?}.", path);
+        return false;
+    }
+
+    // An invalid specification is reported once the chain is added
+    let Ok(genesis_hash) = self::genesis_hash(chain_spec, genesis_hash) else {
+        return false;
+    };
+    let Some(database_genesis_hash) = genesis_hash_field(database) else {
+        warn!("Ignoring database {:?} without a genesis hash.", path);
+        return false;
+    };
+    // The header can't disagree with the database unless it was tampered with
+    let other_genesis_hash = [
+        Some(&database_genesis_hash),
+        header.map(|header| &header.genesis_hash),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|saved| !saved.eq_ignore_ascii_case(genesis_hash));
+    if let Some(other_genesis_hash) = other_genesis_hash {
+        warn!(
+            "Ignoring database {:?} of another chain, whose genesis hash is {} instead of {}.",
+            path, other_genesis_hash, genesis_hash
+        );
+        return false;
+    }
+
+    match header {
+        Some(header) => {
+            if header.smoldot_revision == SMOLDOT_REVISION {
+                if header.crate_version != CRATE_VERSION {
+                    info!(
+                        "Database {:?} was saved by smoldot-flutter {}.",
+                        path, header.crate_version
+                    );
+                }
+                return true;
+            }
+            info!(
+                "Database {:?} was saved at {} by smoldot-flutter {} with smoldot {}, checking that \
+                 smoldot {} can decode it.",
+                path,
+                header.saved_at,
+                header.crate_version,
+                header.smoldot_revision,
+                SMOLDOT_REVISION
+            );
+        }
+        None => info!("Migrating database {:?} saved without a header.", path),
+    }
+
+    let Ok(chain_spec) = parse_chain_spec(chain_spec) else {
+        return false;
+    };
+    let block_number_bytes = usize::from(chain_spec.block_number_bytes());
+    if finalized_block_number(database, block_number_bytes).is_err() {
+        warn!(
+            "Ignoring database {:?} that smoldot {} can't decode.",
+            path, SMOLDOT_REVISION
+        );
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use smoldot::chain::chain_information::{ChainInformation, ValidChainInformation};
+
+    // Returns an empty store in a temporary directory named after the test.
+    pub(crate) fn store(test_name: &str, snapshot_interval: Duration) -> DatabaseStore {
+        let dir = std::env::temp_dir().join(format!(
+            "smoldot-flutter-{}-{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        DatabaseStore::new(dir, snapshot_interval)
+    }
+
+    pub(crate) fn remove_store(store: &DatabaseStore) {
+        fs::remove_dir_all(&store.dir).unwrap();
+    }
+
+    fn chain_spec(name: &str) -> String {
+        fs::read_to_string(format!("../assets/chainspecs/{}.json", name)).unwrap()
+    }
+
+    // Builds a database of the chain of the given specification, whose `chain` can't be decoded.
+    // The database of a parachain has no `chain`.
+    fn database(chain_spec: &str, chain: Option<u32>) -> String {
+        let genesis_hash = genesis_hash_of(&parse_chain_spec(chain_spec).unwrap()).unwrap();
+        let mut database = serde_json::json!({ "genesisHash": hex(&genesis_hash), "nodes": {} });
+        if let Some(chain) = chain {
+            database["chain"] = chain.into();
+        }
+        database.to_string()
+    }
+
+    // Builds a database of the chain of the given specification, whose finalized block is `ahead`
+    // blocks past the checkpoint of the specification.
+    pub(crate) fn checkpoint_database(chain_spec: &str, ahead: u64) -> String {
+        let parsed = parse_chain_spec(chain_spec).unwrap();
+        let checkpoint = parsed.light_sync_state().unwrap();
+        let mut chain_information =
+            ChainInformation::from(checkpoint.to_chain_information().unwrap());
+        chain_information.finalized_block_header.number += ahead;
+        let chain_information = ValidChainInformation::try_from(chain_information).unwrap();
+        let chain = finalized_serialize::encode_chain(
+            &chain_information,
+            usize::from(parsed.block_number_bytes()),
+        );
+        let mut database: Value = serde_json::from_str(&database(chain_spec, None)).unwrap();
+        database["chain"] = serde_json::from_str(&chain).unwrap();
+        database.to_string()
+    }
+
+    #[test]
+    fn computes_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn checks_imported_databases() {
+        let kusama = chain_spec("kusama");
+        let cache = OnceLock::new();
+        let genesis_hash = genesis_hash(&kusama, &cache).unwrap();
+        let check = |database: &str| check(&kusama, genesis_hash, database).unwrap();
+        let usable = |database: &str| match check(database) {
+            Candidate::Usable(finalized_block) => finalized_block,
+            Candidate::Ignored(import) => panic!("{:?}", import),
+        };
+
+        let ignored = [
+            ("{}".to_owned(), DatabaseImport::Malformed),
+            (database(&kusama, Some(1)), DatabaseImport::Malformed),
+            (
+                database(&chain_spec("rococo"), None),
+                DatabaseImport::WrongChain,
+            ),
+            (database(&kusama, None), DatabaseImport::Outdated),
+        ];
+        for (database, import) in ignored {
+            assert_eq!(check(&database), Candidate::Ignored(import), "{}", database);
+        }
+
+        // What the light client made of the database is told by the chain restarted from it
+        let at_checkpoint = checkpoint_database(&kusama, 0);
+        let ahead = checkpoint_database(&kusama, 1);
+        assert_eq!(
+            usable(&at_checkpoint).outcome(&at_checkpoint),
+            DatabaseImport::Outdated
+        );
+        assert_eq!(usable(&ahead).outcome(&ahead), DatabaseImport::Accepted);
+        assert_eq!(
+            usable(&ahead).outcome(&at_checkpoint),
+            DatabaseImport::Malformed
+        );
+    }
+
+    #[test]
+    fn saves_and_loads_databases() {
+        let store = store("saves_and_loads_databases", Duration::from_secs(60));
+        let kusama = chain_spec("kusama");
+        let statemine = chain_spec("statemine");
+        let kusama_genesis_hash = OnceLock::new();
+        let statemine_genesis_hash = OnceLock::new();
+        assert_eq!(store.load("Kusama", &kusama, &kusama_genesis_hash), None);
+
+        // Databases saved by the same revision of smoldot aren't decoded again
+        let second = database(&kusama, Some(2));
+        let other = database(&statemine, Some(3));
+        store.save("Kusama", &database(&kusama, Some(1))).unwrap();
+        store.save("Kusama", &second).unwrap();
+        store.save("Kusama/Statemine", &other).unwrap();
+        assert_eq!(
+            store.load("Kusama", &kusama, &kusama_genesis_hash),
+            Some(second)
+        );
+        assert_eq!(
+            store.load("Kusama/Statemine", &statemine, &statemine_genesis_hash),
+            Some(other)
+        );
+        assert!(store.dir.join("Kusama%2FStatemine.db").exists());
+        assert_eq!(fs::read_dir(&store.dir).unwrap().count(), 2);
+        assert!(store.save("Kusama", "not a database").is_err());
+        // A database that can't be written leaves no temporary file behind
+        fs::create_dir(store.path("Rococo")).unwrap();
+        assert!(store.save("Rococo", &database(&kusama, Some(4))).is_err());
+        assert_eq!(fs::read_dir(&store.dir).unwrap().count(), 3);
+
+        fs::write(store.path("Kusama"), "smoldot-flutter-database 0\nold").unwrap();
+        assert_eq!(store.load("Kusama", &kusama, &kusama_genesis_hash), None);
+        remove_store(&store);
+    }
+
+    // Writes the database of the chain to the store, with the given header or without one.
+    fn write(store: &DatabaseStore, chain_name: &str, database: &str, header: Option<Header>) {
+        fs::create_dir_all(&store.dir).unwrap();
+        let mut file = fs::File::create(store.path(chain_name)).unwrap();
+        match header {
+            Some(header) => header.write(&mut file).unwrap(),
+            None => writeln!(file, "{} {}", MAGIC, LEGACY_FORMAT_VERSION).unwrap(),
+        }
+        file.write_all(database.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn ignores_unusable_databases() {
+        let store = store("ignores_unusable_databases", Duration::from_secs(60));
+        let kusama = chain_spec("kusama");
+        let genesis_hash = OnceLock::new();
+        let database = database(&kusama, Some(1));
+        let write = |content: &str, header| write(&store, "Kusama", content, header);
+
+        write(&database, Some(Header::new(&database).unwrap()));
+        assert_eq!(
+            store.load("Kusama", &kusama, &genesis_hash),
+            Some(database.clone())
+        );
+
+        let corrupted = Header {
+            checksum: !crc32(database.as_bytes()),
+            ..Header::new(&database).unwrap()
+        };
+        write(&database, Some(corrupted));
+        assert_eq!(store.load("Kusama", &kusama, &genesis_hash), None);
+
+        let from_other_revision = Header {
+            smoldot_revision: "other".into(),
+            ..Header::new(&database).unwrap()
+        };
+        write(&database, Some(from_other_revision));
+        assert_eq!(store.load("Kusama", &kusama, &genesis_hash), None);
+
+        write(&database, None);
+        assert_eq!(store.load("Kusama", &kusama, &genesis_hash), None);
+        let decodable = self::database(&kusama, None);
+        write(&decodable, None);
+        assert_eq!(
+            store.load("Kusama", &kusama, &genesis_hash),
+            Some(decodable)
+        );
+        remove_store(&store);
+    }
+
+    #[test]
+    fn ignores_databases_of_other_chains() {
+        let store = store("ignores_databases_of_other_chains", Duration::from_secs(60));
+        let kusama = chain_spec("kusama");
+        let rococo = chain_spec("rococo");
+        let kusama_genesis_hash = OnceLock::new();
+        let rococo_genesis_hash = OnceLock::new();
+        let kusama_database = database(&kusama, None);
+        let rococo_database = database(&rococo, None);
+
+        // The genesis hash of the chain is only computed once a database has been saved for it
+        assert_eq!(store.load("Kusama", &kusama, &kusama_genesis_hash), None);
+        assert_eq!(kusama_genesis_hash.get(), None);
+        store.save("Kusama", &kusama_database).unwrap();
+        assert_eq!(store.load("Kusama", &rococo, &rococo_genesis_hash), None);
+        assert!(rococo_genesis_hash.get().is_some());
+        assert_eq!(
+            store.load("Kusama", &kusama, &kusama_genesis_hash),
+            Some(kusama_database.clone())
+        );
+
+        // The header has to agree with the database
+        let header = Header {
+            genesis_hash: genesis_hash_field(&rococo_database).unwrap(),
+            ..Header::new(&kusama_database).unwrap()
+        };
+        write(&store, "Kusama", &kusama_database, Some(header));
+        assert_eq!(store.load("Kusama", &kusama, &kusama_genesis_hash), None);
+        let header = Header {
+            genesis_hash: genesis_hash_field(&kusama_database).unwrap(),
+            ..Header::new(&rococo_database).unwrap()
+        };
+        write(&store, "Kusama", &rococo_database, Some(header));
+        assert_eq!(store.load("Kusama", &kusama, &kusama_genesis_hash), None);
+
+        write(&store, "Kusama", &rococo_database, None);
+        assert_eq!(store.load("Kusama", &kusama, &kusama_genesis_hash), None);
+        remove_store(&store);
+    }
+}
@@ -0,0 +1,121 @@
+use smoldot_light::{AddChainError, HandleRpcError};
+use std::{fmt, sync::PoisonError};
+
+/// Error returned by the functions exposed to Dart.
+#[derive(Debug)]
+pub struct SmoldotFlutterError {
+    /// What went wrong, for the caller to react to.
+    pub kind: SmoldotFlutterErrorKind,
+    /// Human-readable description of the error.
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmoldotFlutterErrorKind {
+    /// `init_light_client` hasn't been called.
+    NotInitialized,
+    /// `init_light_client` has already been called.
+    AlreadyInitialized,
+    /// No chain with the given name has been started.
+    UnknownChain,
+    /// A chain with the given name has already been started.
+    DuplicateChain,
+    /// The chain specification or database couldn't be used to start the chain.
+    InvalidChainSpec,
+    /// The relay chain of a parachain couldn't be determined.
+    RelayChainNotFound,
+    /// Too many JSON-RPC requests are waiting to be processed by the chain.
+    QueueFull,
+    /// The JSON-RPC request or its parameters are malformed.
+    InvalidRequest,
+    /// The chain was started with its JSON-RPC service disabled.
+    JsonRpcDisabled,
+    /// The chain answered the JSON-RPC request with an error.
+    JsonRpcError,
+    /// The JSON-RPC response couldn't be decoded.
+    MalformedResponse,
+    /// No JSON-RPC response was received within the allotted time.
+    Timeout,
+    /// The chain was removed before the JSON-RPC response was received.
+    ChainRemoved,
+    /// A previous call panicked while holding the state of the client.
+    Poisoned,
+    /// The JSON-RPC server couldn't be started, because the crate was built without its `server`
+    /// feature or because the port is already in use.
+    ServerUnavailable,
+}
+
+impl SmoldotFlutterError {
+    pub(crate) fn new(kind: SmoldotFlutterErrorKind, message: impl Into<String>) -> Self {
+        SmoldotFlutterError {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Prepends a description of what was being attempted to the message.
+    pub(crate) fn context(mut self, context: impl fmt::Display) -> Self {
+        self.message = format!("{} {}", context, self.message);
+        self
+    }
+
+    pub(crate) fn not_initialized() -> Self {
+        Self::new(
+            SmoldotFlutterErrorKind::NotInitialized,
+            "The light client hasn't been initialized.",
+        )
+    }
+
+    pub(crate) fn unknown_chain(chain_name: &str) -> Self {
+        Self::new(
+            SmoldotFlutterErrorKind::UnknownChain,
+            format!("Unknown chain '{:?}'.", chain_name),
+        )
+    }
+
+    pub(crate) fn json_rpc_disabled(chain_name: &str) -> Self {
+        Self::new(
+            SmoldotFlutterErrorKind::JsonRpcDisabled,
+            format!("JSON-RPC is disabled for chain '{:?}'.", chain_name),
+        )
+    }
+}
+
+impl fmt::Display for SmoldotFlutterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for SmoldotFlutterError {}
+
+impl From<AddChainError> for SmoldotFlutterError {
+    fn from(err: AddChainError) -> Self {
+        let kind = match err {
+            AddChainError::NoRelayChainFound | AddChainError::MultipleRelayChains => {
+                SmoldotFlutterErrorKind::RelayChainNotFound
+            }
+            _ => SmoldotFlutterErrorKind::InvalidChainSpec,
+        };
+        Self::new(kind, err.to_string())
+    }
+}
+
+impl From<HandleRpcError> for SmoldotFlutterError {
+    fn from(err: HandleRpcError) -> Self {
+        let kind = match err {
+            HandleRpcError::TooManyPendingRequests { .. } => SmoldotFlutterErrorKind::QueueFull,
+            HandleRpcError::MalformedJsonRpc(_) => SmoldotFlutterErrorKind::InvalidRequest,
+        };
+        Self::new(kind, err.to_string())
+    }
+}
+
+impl<T> From<PoisonError<T>> for SmoldotFlutterError {
+    fn from(_: PoisonError<T>) -> Self {
+        Self::new(
+            SmoldotFlutterErrorKind::Poisoned,
+            "The state of the light client is poisoned by an earlier panic.",
+        )
+    }
+}
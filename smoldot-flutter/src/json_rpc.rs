@@ -0,0 +1,812 @@
+use crate::broadcaster::Broadcaster;
+use crate::error::{SmoldotFlutterError, SmoldotFlutterErrorKind};
+use async_std::channel::{self, Receiver, Sender};
+use flutter_rust_bridge::StreamSink;
+use futures_util::future::{self, Either};
+use log::{debug, warn};
+use serde_json::Value;
+use smoldot_light::JsonRpcResponses;
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+// Prefix of the ids allocated for requests issued through `json_rpc_call` and `subscribe`, so
+// that they can't be mistaken for ids chosen by callers of `send_json_rpc_request`.
+const REQUEST_ID_PREFIX: &str = "smoldot-flutter:";
+
+// Method of the notification telling a connection to the JSON-RPC server that one of its
+// subscriptions has ended, as the chain was restarted.
+#[cfg(feature = "server")]
+const SUBSCRIPTION_CLOSED_METHOD: &str = "smoldotFlutter_subscriptionClosed";
+
+/// Event of a subscription started through `subscribe`.
+pub enum SubscriptionEvent {
+    /// The `result` of a notification, as JSON text.
+    Notification(String),
+    /// Why the subscription couldn't be started, after which the stream ends.
+    Err(SmoldotFlutterError),
+}
+
+/// Subscription whose notifications are forwarded to a dedicated sink.
+pub(crate) struct Subscription {
+    pub(crate) sink: SubscriptionSink,
+    pub(crate) unsubscribe_method: String,
+}
+
+pub(crate) enum SubscriptionSink {
+    /// Stream of a subscription started through `subscribe`, which receives the `result` of each
+    /// notification.
+    /// The sink is shared with `subscribe`, which reports through it why the subscription
+    /// couldn't be started.
+    Stream(Arc<StreamSink<SubscriptionEvent>>),
+    /// Connection to the JSON-RPC server, identified by its id, which receives each notification
+    /// as is.
+    #[cfg(feature = "server")]
+    Connection(u64, Sender<String>),
+    /// Task following the chain on behalf of the crate, such as the one of `watch_chain_status`,
+    /// which receives the `result` of each notification. The channel must be unbounded, as a
+    /// notification that can't be sent cancels the subscription.
+    Task(Sender<Value>),
+}
+
+impl SubscriptionSink {
+    // Forwards a notification, returning `false` if the sink is closed.
+    fn add(&self, notification: &Value) -> bool {
+        match self {
+            SubscriptionSink::Stream(sink) => sink.add(SubscriptionEvent::Notification(
+                notification["params"]["result"].to_string(),
+            )),
+            #[cfg(feature = "server")]
+            SubscriptionSink::Connection(_, sender) => {
+                sender.try_send(notification.to_string()).is_ok()
+            }
+            SubscriptionSink::Task(sender) => sender
+                .try_send(notification["params"]["result"].clone())
+                .is_ok(),
+        }
+    }
+
+    fn close(&self) {
+        match self {
+            SubscriptionSink::Stream(sink) => {
+                sink.close();
+            }
+            #[cfg(feature = "server")]
+            SubscriptionSink::Connection(_, sender) => {
+                sender.close();
+            }
+            SubscriptionSink::Task(sender) => {
+                sender.close();
+            }
+        }
+    }
+
+    // Called when the request meant to start the subscription didn't. A connection carries on, as
+    // it still receives the response to its request, and so does a stream, which `subscribe`
+    // closes once it has reported the error. The other sinks are closed.
+    fn abandon(&self) {
+        if let SubscriptionSink::Task(_) = self {
+            self.close();
+        }
+    }
+
+    // Called once the chain stops sending notifications. A connection carries on, as it follows
+    // the chain when it is restarted, and is only told that the subscription has ended. The
+    // other sinks are closed.
+    #[allow(unused_variables)]
+    fn end(&self, subscription_id: &str) {
+        match self {
+            #[cfg(feature = "server")]
+            SubscriptionSink::Connection(_, sender) => {
+                let notification = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "method": SUBSCRIPTION_CLOSED_METHOD,
+                    "params": { "subscription": subscription_id },
+                });
+                let _ = sender.try_send(notification.to_string());
+            }
+            _ => self.close(),
+        }
+    }
+
+    #[cfg(feature = "server")]
+    fn connection_id(&self) -> Option<u64> {
+        match self {
+            SubscriptionSink::Connection(connection_id, _) => Some(*connection_id),
+            _ => None,
+        }
+    }
+}
+
+struct PendingRequest {
+    response: Sender<String>,
+    // Set if the request starts a subscription, which is then registered as soon as the
+    // response is routed, so that no notification can be missed.
+    subscription: Option<Subscription>,
+    // Whether the caller stopped waiting for the response, in which case the subscription the
+    // request might still start is cancelled right away.
+    given_up: bool,
+}
+
+/// Routes the responses and notifications of a chain: requests issued through `json_rpc_call`
+/// waiting for their response, and subscriptions started through `subscribe`.
+pub(crate) struct Router {
+    next_id: AtomicU64,
+    requests: Mutex<HashMap<String, PendingRequest>>,
+    subscriptions: Mutex<HashMap<String, Subscription>>,
+}
+
+impl Router {
+    pub(crate) fn new() -> Self {
+        Router {
+            next_id: AtomicU64::new(0),
+            requests: Mutex::new(HashMap::new()),
+            subscriptions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Allocates a new request id and returns it, alongside the receiver on which the matching
+    /// response will be delivered.
+    pub(crate) fn register(
+        &self,
+        subscription: Option<Subscription>,
+    ) -> (String, Receiver<String>) {
+        let id = format!(
+            "{}{}",
+            REQUEST_ID_PREFIX,
+            self.next_id.fetch_add(1, Ordering::Relaxed)
+        );
+        let (tx, rx) = channel::bounded(1);
+        self.requests.lock().unwrap().insert(
+            id.clone(),
+            PendingRequest {
+                response: tx,
+                subscription,
+                given_up: false,
+            },
+        );
+        (id, rx)
+    }
+
+    /// Forgets about a request that couldn't be sent.
+    pub(crate) fn cancel(&self, id: &str) {
+        self.requests.lock().unwrap().remove(id);
+    }
+
+    /// Stops waiting for the response to a request that was sent, for example because it timed
+    /// out. If the request starts a subscription, it is kept track of until its response arrives,
+    /// so that the subscription can be cancelled.
+    pub(crate) fn give_up(&self, id: &str) {
+        let mut requests = self.requests.lock().unwrap();
+        match requests.get_mut(id) {
+            Some(request) if request.subscription.is_some() => request.given_up = true,
+            _ => {
+                requests.remove(id);
+            }
+        }
+    }
+
+    /// Builds the requests that cancel every active subscription, and ends them.
+    pub(crate) fn unsubscribe_all(&self) -> Vec<String> {
+        let subscriptions = mem::take(&mut *self.subscriptions.lock().unwrap());
+        subscriptions
+            .into_iter()
+            .map(|(subscription_id, subscription)| {
+                subscription.sink.end(&subscription_id);
+                self.unsubscribe_request(&subscription_id, &subscription.unsubscribe_method)
+            })
+            .collect()
+    }
+
+    /// Forgets about a subscription that a connection to the JSON-RPC server has cancelled itself.
+    /// Subscriptions of other connections or sinks are left alone.
+    #[cfg(feature = "server")]
+    pub(crate) fn forget(&self, connection_id: u64, subscription_id: &str) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if subscriptions
+            .get(subscription_id)
+            .and_then(|subscription| subscription.sink.connection_id())
+            == Some(connection_id)
+        {
+            subscriptions.remove(subscription_id);
+        }
+    }
+
+    /// Builds the requests that cancel the subscriptions of a connection to the JSON-RPC server
+    /// that has been closed, and forgets about them.
+    #[cfg(feature = "server")]
+    pub(crate) fn unsubscribe_connection(&self, connection_id: u64) -> Vec<String> {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        let subscription_ids: Vec<String> = subscriptions
+            .iter()
+            .filter(|(_, subscription)| subscription.sink.connection_id() == Some(connection_id))
+            .map(|(subscription_id, _)| subscription_id.clone())
+            .collect();
+        subscription_ids
+            .into_iter()
+            .map(|subscription_id| {
+                let subscription = subscriptions.remove(&subscription_id).unwrap();
+                self.unsubscribe_request(&subscription_id, &subscription.unsubscribe_method)
+            })
+            .collect()
+    }
+
+    // The response to an unsubscribe request is of no interest, but is still routed here so that
+    // it doesn't end up in the stream of `listen_json_rpc_responses`.
+    fn unsubscribe_request(&self, subscription_id: &str, unsubscribe_method: &str) -> String {
+        let (id, _) = self.register(None);
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": unsubscribe_method,
+            "params": [subscription_id],
+        })
+        .to_string()
+    }
+
+    /// Delivers the message to the pending request or subscription it belongs to. The message is
+    /// given back if it doesn't belong to any.
+    fn route(&self, message: String) -> Routed {
+        let message_json: Value = match serde_json::from_str(&message) {
+            Ok(message_json) => message_json,
+            Err(_) => return Routed::Unrouted(message),
+        };
+
+        if let Some(subscription_id) = notification_subscription(&message_json) {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            if let Some(subscription) = subscriptions.get(&subscription_id) {
+                if subscription.sink.add(&message_json) {
+                    return Routed::Delivered;
+                }
+                // The Dart side has cancelled its stream, or the connection has been closed.
+                let subscription = subscriptions.remove(&subscription_id).unwrap();
+                return Routed::Unsubscribe(
+                    self.unsubscribe_request(&subscription_id, &subscription.unsubscribe_method),
+                );
+            }
+            return Routed::Unrouted(message);
+        }
+
+        let pending_request = match message_json.get("id").and_then(Value::as_str) {
+            Some(id) if id.starts_with(REQUEST_ID_PREFIX) => {
+                self.requests.lock().unwrap().remove(id)
+            }
+            _ => None,
+        };
+        let pending_request = match pending_request {
+            Some(pending_request) => pending_request,
+            None => return Routed::Unrouted(message),
+        };
+
+        if let Some(subscription) = pending_request.subscription {
+            match subscription_id(&message_json["result"]) {
+                Some(subscription_id) if pending_request.given_up => {
+                    return Routed::Unsubscribe(
+                        self.unsubscribe_request(
+                            &subscription_id,
+                            &subscription.unsubscribe_method,
+                        ),
+                    );
+                }
+                Some(subscription_id) => {
+                    self.subscriptions
+                        .lock()
+                        .unwrap()
+                        .insert(subscription_id, subscription);
+                }
+                None => {
+                    subscription.sink.abandon();
+                }
+            }
+        }
+
+        // The receiving side might have given up waiting in the meantime, in which case the
+        // response is simply discarded.
+        let _ = pending_request.response.try_send(message);
+        Routed::Delivered
+    }
+
+    /// Drops every pending request and subscription, notifying the waiting callers and the
+    /// subscribers that nothing more will come.
+    fn clear(&self) {
+        self.requests.lock().unwrap().clear();
+        for (subscription_id, subscription) in self.subscriptions.lock().unwrap().drain() {
+            subscription.sink.end(&subscription_id);
+        }
+    }
+}
+
+enum Routed {
+    Delivered,
+    Unrouted(String),
+    /// The message was meant for a subscription that is no longer listened to, which must be
+    /// cancelled by sending the given request.
+    Unsubscribe(String),
+}
+
+// Error codes defined by the JSON-RPC 2.0 specification.
+const INVALID_REQUEST: i64 = -32600;
+const SERVER_ERROR: i64 = -32000;
+
+/// Splits a JSON-RPC batch into its requests, or returns `None` if `request` isn't a batch.
+pub(crate) fn parse_batch(request: &str) -> Result<Option<Vec<Value>>, SmoldotFlutterError> {
+    match serde_json::from_str(request) {
+        Ok(Value::Array(requests)) if requests.is_empty() => Err(SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::InvalidRequest,
+            "Empty JSON-RPC batch.",
+        )),
+        Ok(Value::Array(requests)) => Ok(Some(requests)),
+        _ => Ok(None),
+    }
+}
+
+/// Rejects a request whose id uses the prefix of the ids allocated by routers, as its response
+/// would be taken for the response to the request the router issued under the same id.
+pub(crate) fn check_request_id(request: &str) -> Result<(), SmoldotFlutterError> {
+    if !request.contains(REQUEST_ID_PREFIX) {
+        return Ok(());
+    }
+    let request: Value = match serde_json::from_str(request) {
+        Ok(request) => request,
+        Err(_) => return Ok(()),
+    };
+    match request.get("id").and_then(Value::as_str) {
+        Some(id) if id.starts_with(REQUEST_ID_PREFIX) => Err(SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::InvalidRequest,
+            format!(
+                "JSON-RPC request ids starting with '{}' are reserved.",
+                REQUEST_ID_PREFIX
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The requests of a JSON-RPC batch, whose responses are reassembled into a single reply.
+///
+/// Each request is sent with an id allocated by the router, so that its response can't be mixed
+/// up with the response to another request that has the same id, and gets its own id back in the
+/// reply.
+pub(crate) struct Batch {
+    responses: Vec<BatchResponse>,
+}
+
+enum BatchResponse {
+    Pending {
+        id: Value,
+        response: Receiver<String>,
+    },
+    // The request couldn't be sent, and is answered with an error instead.
+    Ready(Value),
+}
+
+impl Batch {
+    pub(crate) fn new() -> Self {
+        Batch {
+            responses: Vec::new(),
+        }
+    }
+
+    /// Sends a request of the batch through `send`. A request that can't be sent is answered with
+    /// an error in the reply, unless it is a notification, which isn't answered at all.
+    ///
+    /// `subscription` is registered if the request turns out to start a subscription. Returns the
+    /// position of the response to the request in the reply, if it gets one.
+    pub(crate) fn send(
+        &mut self,
+        router: &Router,
+        mut request: Value,
+        subscription: Option<Subscription>,
+        send: impl Fn(String) -> Result<(), SmoldotFlutterError>,
+    ) -> Option<usize> {
+        let position = self.responses.len();
+        let Some(object) = request.as_object_mut() else {
+            self.responses.push(BatchResponse::Ready(error_response(
+                Value::Null,
+                INVALID_REQUEST,
+                "Invalid request",
+            )));
+            return Some(position);
+        };
+        let Some(id) = object.get("id").cloned() else {
+            if let Err(err) = send(request.to_string()) {
+                debug!("Failed to send JSON-RPC notification of batch: {:?}", err);
+            }
+            return None;
+        };
+
+        let (routed_id, response) = router.register(subscription);
+        object.insert("id".to_owned(), Value::String(routed_id.clone()));
+        match send(request.to_string()) {
+            Ok(()) => self.responses.push(BatchResponse::Pending { id, response }),
+            Err(err) => {
+                router.cancel(&routed_id);
+                let code = match err.kind {
+                    SmoldotFlutterErrorKind::InvalidRequest => INVALID_REQUEST,
+                    _ => SERVER_ERROR,
+                };
+                self.responses
+                    .push(BatchResponse::Ready(error_response(id, code, &err.message)));
+            }
+        }
+        Some(position)
+    }
+
+    /// Waits for the response to every request of the batch, and returns them in the order of the
+    /// requests. Returns `None` if there is nothing to reply, either because the batch only
+    /// contains notifications or because the chain was removed in the meantime.
+    pub(crate) async fn reply(self) -> Option<Vec<Value>> {
+        if self.responses.is_empty() {
+            return None;
+        }
+        let mut reply = Vec::with_capacity(self.responses.len());
+        for response in self.responses {
+            reply.push(match response {
+                BatchResponse::Pending { id, response } => {
+                    let response = response.recv().await.ok()?;
+                    let mut response: Value =
+                        serde_json::from_str(&response).unwrap_or_else(|_| {
+                            error_response(Value::Null, SERVER_ERROR, "Malformed response")
+                        });
+                    response["id"] = id;
+                    response
+                }
+                BatchResponse::Ready(response) => response,
+            });
+        }
+        Some(reply)
+    }
+}
+
+pub(crate) fn error_response(id: Value, code: i64, message: &str) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": code,
+            "message": message,
+        },
+    })
+}
+
+/// Builds a JSON-RPC request with the given id, method and parameters.
+pub(crate) fn build_request(
+    id: &str,
+    method: &str,
+    params: &str,
+) -> Result<String, SmoldotFlutterError> {
+    let params: Value = match serde_json::from_str(params) {
+        Ok(params @ (Value::Array(_) | Value::Object(_))) => params,
+        _ => {
+            return Err(SmoldotFlutterError::new(
+                SmoldotFlutterErrorKind::InvalidRequest,
+                format!("Invalid JSON-RPC parameters: {}", params),
+            ))
+        }
+    };
+
+    Ok(serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": method,
+        "params": params,
+    })
+    .to_string())
+}
+
+/// Extracts the `result` of a JSON-RPC response, as JSON text.
+pub(crate) fn parse_result(response: &str) -> Result<String, SmoldotFlutterError> {
+    let mut response: Value =
+        serde_json::from_str(response).map_err(|_| malformed_response(response))?;
+
+    if let Some(error) = response.get("error") {
+        return Err(SmoldotFlutterError::new(
+            SmoldotFlutterErrorKind::JsonRpcError,
+            format!(
+                "JSON-RPC error {}: {}",
+                error
+                    .get("code")
+                    .and_then(Value::as_i64)
+                    .unwrap_or_default(),
+                error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+            ),
+        ));
+    }
+
+    match response.get_mut("result") {
+        Some(result) => Ok(result.take().to_string()),
+        None => Err(malformed_response(&response.to_string())),
+    }
+}
+
+fn malformed_response(response: &str) -> SmoldotFlutterError {
+    SmoldotFlutterError::new(
+        SmoldotFlutterErrorKind::MalformedResponse,
+        format!("Malformed JSON-RPC response: {}", response),
+    )
+}
+
+// Subscription ids are opaque, and might be either strings or numbers.
+fn subscription_id(value: &Value) -> Option<String> {
+    match value {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+fn notification_subscription(message: &Value) -> Option<String> {
+    if message.get("id").is_some() || message.get("method").is_none() {
+        return None;
+    }
+    subscription_id(message.get("params")?.get("subscription")?)
+}
+
+/// Pulls the responses of a chain for as long as it exists, delivering answers to requests issued
+/// through `json_rpc_call` to their caller, notifications to the subscriptions started through
+/// `subscribe`, and everything else (other notifications, responses to requests sent with
+/// `send_json_rpc_request`) to the listeners of `broadcaster`.
+///
+/// `send_request` is used to cancel the subscriptions that are no longer listened to. The routing
+/// stops early once `stopped` is closed, for example because the chain is being replaced.
+pub(crate) async fn route_responses(
+    chain_name: String,
+    mut responses: JsonRpcResponses,
+    router: Arc<Router>,
+    broadcaster: Arc<Broadcaster>,
+    stopped: Receiver<()>,
+    send_request: impl Fn(String) -> Result<(), SmoldotFlutterError>,
+) {
+    let stopped = stopped.recv();
+    futures_util::pin_mut!(stopped);
+    loop {
+        let next = responses.next();
+        futures_util::pin_mut!(next);
+        let response = match future::select(next, stopped.as_mut()).await {
+            Either::Left((Some(response), _)) => response,
+            _ => break,
+        };
+        match router.route(response) {
+            Routed::Delivered => {}
+            Routed::Unrouted(response) => broadcaster.broadcast(response).await,
+            Routed::Unsubscribe(request) => {
+                if let Err(err) = send_request(request) {
+                    warn!(
+                        "Failed to cancel subscription on chain '{:?}': {:?}",
+                        chain_name, err
+                    );
+                }
+            }
+        }
+    }
+    router.clear();
+    broadcaster.detach_all();
+    debug!(
+        "JSON-RPC response routing for chain '{:?}' has ended.",
+        chain_name
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_responses_to_pending_requests() {
+        let router = Router::new();
+        let (id, response) = router.register(None);
+
+        let request = build_request(&id, "system_health", "[]").unwrap();
+        let request: Value = serde_json::from_str(&request).unwrap();
+        assert_eq!(request["id"], id);
+        assert_eq!(request["method"], "system_health");
+
+        let answer = format!(
+            r#"{{"jsonrpc":"2.0","id":"{}","result":{{"peers":3}}}}"#,
+            id
+        );
+        assert!(matches!(router.route(answer.clone()), Routed::Delivered));
+        assert_eq!(response.try_recv().unwrap(), answer);
+        assert_eq!(parse_result(&answer).unwrap(), r#"{"peers":3}"#);
+    }
+
+    #[test]
+    fn leaves_other_messages_unrouted() {
+        let router = Router::new();
+        let (_id, _response) = router.register(None);
+
+        let answer = r#"{"jsonrpc":"2.0","id":1,"result":"0x00"}"#;
+        assert!(matches!(
+            router.route(answer.to_owned()),
+            Routed::Unrouted(message) if message == answer
+        ));
+
+        let notification = r#"{"jsonrpc":"2.0","method":"chain_newHead","params":{"subscription":"a","result":{}}}"#;
+        assert!(matches!(
+            router.route(notification.to_owned()),
+            Routed::Unrouted(message) if message == notification
+        ));
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn tracks_subscriptions_of_connections() {
+        let router = Router::new();
+        let (messages, queued) = channel::unbounded();
+        let subscribe = |subscription_id: Value| {
+            let (id, _response) = router.register(Some(Subscription {
+                sink: SubscriptionSink::Connection(1, messages.clone()),
+                unsubscribe_method: "chain_unsubscribeNewHeads".into(),
+            }));
+            let answer = serde_json::json!({"jsonrpc":"2.0","id":id,"result":subscription_id});
+            assert!(matches!(
+                router.route(answer.to_string()),
+                Routed::Delivered
+            ));
+        };
+
+        // A request that doesn't start a subscription leaves the connection open.
+        subscribe(Value::Null);
+        assert!(!messages.is_closed());
+        subscribe("a".into());
+        subscribe("b".into());
+
+        let notification = r#"{"jsonrpc":"2.0","method":"chain_newHead","params":{"subscription":"a","result":{}}}"#;
+        assert!(matches!(
+            router.route(notification.to_owned()),
+            Routed::Delivered
+        ));
+        let forwarded: Value = serde_json::from_str(&queued.try_recv().unwrap()).unwrap();
+        assert_eq!(forwarded["params"]["subscription"], "a");
+
+        router.forget(2, "a");
+        router.forget(1, "a");
+        assert!(matches!(
+            router.route(notification.to_owned()),
+            Routed::Unrouted(_)
+        ));
+
+        let requests = router.unsubscribe_connection(1);
+        assert_eq!(requests.len(), 1);
+        let request: Value = serde_json::from_str(&requests[0]).unwrap();
+        assert_eq!(request["method"], "chain_unsubscribeNewHeads");
+        assert_eq!(request["params"], serde_json::json!(["b"]));
+        assert!(router.subscriptions.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn cancels_subscriptions_started_too_late() {
+        let router = Router::new();
+        let (notifications, _received) = channel::unbounded();
+        let (id, response) = router.register(Some(Subscription {
+            sink: SubscriptionSink::Task(notifications),
+            unsubscribe_method: "chain_unsubscribeNewHeads".into(),
+        }));
+        router.give_up(&id);
+        drop(response);
+
+        let answer = serde_json::json!({"jsonrpc":"2.0","id":id,"result":"a"});
+        let Routed::Unsubscribe(request) = router.route(answer.to_string()) else {
+            panic!("late subscription not cancelled");
+        };
+        let request: Value = serde_json::from_str(&request).unwrap();
+        assert_eq!(request["method"], "chain_unsubscribeNewHeads");
+        assert_eq!(request["params"], serde_json::json!(["a"]));
+        assert!(router.subscriptions.lock().unwrap().is_empty());
+
+        // Requests that don't start a subscription are forgotten right away
+        let (id, _response) = router.register(None);
+        router.give_up(&id);
+        assert!(router.requests.lock().unwrap().get(&id).is_none());
+    }
+
+    #[test]
+    fn rejects_reserved_request_ids() {
+        let request = build_request("smoldot-flutter:1", "system_chain", "[]").unwrap();
+        let err = check_request_id(&request).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::InvalidRequest);
+
+        let request = build_request("1", "system_chain", r#"["smoldot-flutter:1"]"#).unwrap();
+        assert!(check_request_id(&request).is_ok());
+        assert!(check_request_id("{").is_ok());
+    }
+
+    #[test]
+    fn reassembles_batch_responses_in_order() {
+        let router = Router::new();
+        let sent = Mutex::new(Vec::new());
+        let send = |request: String| {
+            let request: Value = serde_json::from_str(&request).unwrap();
+            if request["method"] == "rejected" {
+                return Err(SmoldotFlutterError::new(
+                    SmoldotFlutterErrorKind::QueueFull,
+                    "Too many requests.",
+                ));
+            }
+            sent.lock().unwrap().push(request);
+            Ok(())
+        };
+
+        let requests = parse_batch(
+            r#"[
+                {"jsonrpc":"2.0","id":1,"method":"system_name","params":[]},
+                {"jsonrpc":"2.0","method":"system_health","params":[]},
+                {"jsonrpc":"2.0","id":1,"method":"rejected","params":[]},
+                2,
+                {"jsonrpc":"2.0","id":"b","method":"system_version","params":[]}
+            ]"#,
+        )
+        .unwrap()
+        .unwrap();
+        let mut batch = Batch::new();
+        for request in requests {
+            batch.send(&router, request, None, send);
+        }
+
+        // Answer the requests in reverse order, each of them with its method name.
+        let sent = sent.into_inner().unwrap();
+        assert_eq!(sent.len(), 3);
+        assert!(sent[1].get("id").is_none());
+        for request in [&sent[2], &sent[0]] {
+            let answer = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": request["method"],
+            });
+            assert!(matches!(
+                router.route(answer.to_string()),
+                Routed::Delivered
+            ));
+        }
+
+        let reply = async_std::task::block_on(batch.reply()).unwrap();
+        assert_eq!(
+            Value::Array(reply),
+            serde_json::json!([
+                {"jsonrpc":"2.0","id":1,"result":"system_name"},
+                {"jsonrpc":"2.0","id":1,"error":{"code":SERVER_ERROR,"message":"Too many requests."}},
+                {"jsonrpc":"2.0","id":null,"error":{"code":INVALID_REQUEST,"message":"Invalid request"}},
+                {"jsonrpc":"2.0","id":"b","result":"system_version"},
+            ])
+        );
+    }
+
+    #[test]
+    fn tells_batches_apart() {
+        assert_eq!(
+            parse_batch("[]").unwrap_err().kind,
+            SmoldotFlutterErrorKind::InvalidRequest
+        );
+        assert!(
+            parse_batch(r#"{"jsonrpc":"2.0","id":1,"method":"system_name"}"#)
+                .unwrap()
+                .is_none()
+        );
+        assert!(parse_batch("not json").unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        assert_eq!(
+            build_request("id", "system_health", "1").unwrap_err().kind,
+            SmoldotFlutterErrorKind::InvalidRequest
+        );
+    }
+
+    #[test]
+    fn reports_json_rpc_errors() {
+        let answer =
+            r#"{"jsonrpc":"2.0","id":"x","error":{"code":-32601,"message":"Method not found"}}"#;
+        let err = parse_result(answer).unwrap_err();
+        assert_eq!(err.kind, SmoldotFlutterErrorKind::JsonRpcError);
+        assert!(err.message.contains("-32601"));
+    }
+}
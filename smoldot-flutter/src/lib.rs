@@ -1,3 +1,13 @@
-mod api;
+pub mod api;
+pub mod blocking;
+mod broadcaster;
+mod database;
 mod bridge_generated; /* AUTO INJECTED BY flutter_rust_bridge. This line may not be accurate, and you can change it according to your needs. */
+pub mod error;
+mod json_rpc;
 mod logger;
+pub mod platform;
+#[cfg(feature = "server")]
+mod server;
+mod status;
+mod util;
@@ -0,0 +1,603 @@
+use futures_util::{
+    future::{self, BoxFuture},
+    AsyncRead, AsyncWrite, FutureExt as _,
+};
+use smoldot::libp2p::websocket;
+use smoldot_light::platform::{
+    with_buffers, Address, ConnectError, ConnectionType, IpAddr, MultiStreamAddress,
+    MultiStreamWebRtcConnection, PlatformRef, SubstreamDirection,
+};
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant, UNIX_EPOCH},
+};
+
+// Granularity to which the timers of a throttled platform are aligned, unless configured otherwise.
+const DEFAULT_BACKGROUND_TIMER_RESOLUTION: Duration = Duration::from_secs(5);
+
+// Adjusts the platform of the light clients created through the API, if set through `customize`.
+static CUSTOMIZE: RwLock<Option<Arc<Customize>>> = RwLock::new(None);
+
+type Customize = dyn Fn(PlatformBuilder) -> PlatformBuilder + Send + Sync;
+
+/// Registers a function replacing parts of the platform of every light client created afterwards
+/// through `init_light_client` or `create_light_client`, for example to route networking through
+/// the APIs of the host OS. The function is given the builder of the platform once the
+/// `LightClientConfig` of the client has been applied to it.
+///
+/// This is meant to be called by the Rust side of the app, before the Dart side creates its light
+/// clients. Registering another function replaces the previous one.
+pub fn customize(customize: impl Fn(PlatformBuilder) -> PlatformBuilder + Send + Sync + 'static) {
+    *CUSTOMIZE.write().unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(customize));
+}
+
+/// Runs the background tasks of the client.
+pub trait Executor: Send + Sync {
+    fn spawn(&self, task_name: &str, task: BoxFuture<'static, ()>);
+}
+
+/// Tells the time and provides timers.
+pub trait Clock: Send + Sync {
+    /// Time elapsed since the UNIX epoch.
+    fn now_from_unix_epoch(&self) -> Duration;
+
+    /// Monotonic time, which must never go backwards.
+    fn now(&self) -> Instant;
+
+    /// Returns a future that completes once `now` has reached `when`.
+    fn sleep_until(&self, when: Instant) -> BoxFuture<'static, ()>;
+}
+
+/// Source of the randomness used for example to generate the networking keys of the client.
+pub trait RandomSource: Send + Sync {
+    fn fill_bytes(&self, buffer: &mut [u8]);
+}
+
+/// Opens the connections to the peers of the chains.
+pub trait Connector: Send + Sync {
+    /// Whether `connect` can open connections of the given type.
+    fn supports(&self, connection_type: ConnectionType) -> bool;
+
+    fn connect(&self, address: Address) -> BoxFuture<'static, Result<Connection, ConnectError>>;
+}
+
+/// Connection opened by a `Connector`.
+pub type Connection = Pin<Box<dyn AsyncReadWrite + Send>>;
+
+pub trait AsyncReadWrite: AsyncRead + AsyncWrite {}
+
+impl<T: AsyncRead + AsyncWrite> AsyncReadWrite for T {}
+
+/// Platform bindings of the light client, made of parts that can be replaced to control how the
+/// background tasks are run, how time is measured, where randomness comes from and how the
+/// connections to peers are opened.
+///
+/// The background work of the client can be limited while the app is in the background, through
+/// `set_throttled`.
+#[derive(Clone)]
+pub(crate) struct Platform(Arc<Parts>);
+
+struct Parts {
+    client_name: String,
+    client_version: String,
+    executor: Box<dyn Executor>,
+    clock: Box<dyn Clock>,
+    random_source: Box<dyn RandomSource>,
+    connector: Box<dyn Connector>,
+    background_timer_resolution: Duration,
+    // Instant to which the timers of the client are aligned while throttled.
+    origin: Instant,
+    throttled: AtomicBool,
+}
+
+impl Platform {
+    /// Returns a builder for a platform running its tasks on async-std, using the system clock and
+    /// randomness and connecting to peers over TCP and WebSocket, as `DefaultPlatform` does, unless
+    /// some of these parts are replaced.
+    pub(crate) fn builder(client_name: String, client_version: String) -> PlatformBuilder {
+        PlatformBuilder {
+            client_name,
+            client_version,
+            executor: Box::new(AsyncStdExecutor),
+            clock: Box::new(SystemClock),
+            random_source: Box::new(OsRandomSource),
+            connector: Box::new(TcpConnector::new(true, true)),
+            background_timer_resolution: DEFAULT_BACKGROUND_TIMER_RESOLUTION,
+        }
+    }
+
+    /// Sets whether the background work of the client is limited, for example while the app is in
+    /// the background. While throttled, the timers of the client are postponed to the next
+    /// multiple of the background timer resolution, so that they fire together rather than waking
+    /// the client up one after the other. The timers already running aren't affected, and the
+    /// data received from peers is still processed as it comes in.
+    pub(crate) fn set_throttled(&self, throttled: bool) {
+        self.0.throttled.store(throttled, Ordering::Relaxed);
+    }
+
+    // Returns the instant at which a timer set to fire at `when` actually fires.
+    fn deadline(&self, when: Instant) -> Instant {
+        let resolution = self.0.background_timer_resolution.as_nanos();
+        if !self.0.throttled.load(Ordering::Relaxed) || resolution == 0 {
+            return when;
+        }
+        let remainder = when.saturating_duration_since(self.0.origin).as_nanos() % resolution;
+        if remainder == 0 {
+            return when;
+        }
+        when + Duration::from_nanos((resolution - remainder) as u64)
+    }
+}
+
+/// Builder of the platform of a light client, whose parts can be replaced through `customize`.
+pub struct PlatformBuilder {
+    client_name: String,
+    client_version: String,
+    executor: Box<dyn Executor>,
+    clock: Box<dyn Clock>,
+    random_source: Box<dyn RandomSource>,
+    connector: Box<dyn Connector>,
+    background_timer_resolution: Duration,
+}
+
+impl PlatformBuilder {
+    pub fn executor(mut self, executor: impl Executor + 'static) -> Self {
+        self.executor = Box::new(executor);
+        self
+    }
+
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    pub fn random_source(mut self, random_source: impl RandomSource + 'static) -> Self {
+        self.random_source = Box::new(random_source);
+        self
+    }
+
+    pub fn connector(mut self, connector: impl Connector + 'static) -> Self {
+        self.connector = Box::new(connector);
+        self
+    }
+
+    /// Sets the granularity to which the timers of the client are aligned while it is throttled.
+    /// Defaults to 5 seconds. Timers aren't aligned at all if zero.
+    pub fn background_timer_resolution(mut self, resolution: Duration) -> Self {
+        self.background_timer_resolution = resolution;
+        self
+    }
+
+    // Applies the function registered through `customize`, if any.
+    pub(crate) fn customized(self) -> Self {
+        let customize = CUSTOMIZE
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone();
+        match customize {
+            Some(customize) => customize(self),
+            None => self,
+        }
+    }
+
+    pub(crate) fn build(self) -> Platform {
+        let origin = self.clock.now();
+        Platform(Arc::new(Parts {
+            client_name: self.client_name,
+            client_version: self.client_version,
+            executor: self.executor,
+            clock: self.clock,
+            random_source: self.random_source,
+            connector: self.connector,
+            background_timer_resolution: self.background_timer_resolution,
+            origin,
+            throttled: AtomicBool::new(false),
+        }))
+    }
+}
+
+impl PlatformRef for Platform {
+    type Delay = BoxFuture<'static, ()>;
+    type Instant = Instant;
+    // Multi-stream connections are only used by WebRTC, which isn't supported.
+    type MultiStream = Infallible;
+    type Stream = with_buffers::WithBuffers<Connection>;
+    type StreamConnectFuture = BoxFuture<'static, Result<Self::Stream, ConnectError>>;
+    type MultiStreamConnectFuture =
+        BoxFuture<'static, Result<MultiStreamWebRtcConnection<Self::MultiStream>, ConnectError>>;
+    type ReadWriteAccess<'a> = with_buffers::ReadWriteAccess<'a>;
+    type StreamUpdateFuture<'a> = BoxFuture<'a, ()>;
+    type StreamErrorRef<'a> = &'a io::Error;
+    type NextSubstreamFuture<'a> = future::Pending<Option<(Self::Stream, SubstreamDirection)>>;
+
+    fn now_from_unix_epoch(&self) -> Duration {
+        self.0.clock.now_from_unix_epoch()
+    }
+
+    fn now(&self) -> Self::Instant {
+        self.0.clock.now()
+    }
+
+    fn fill_random_bytes(&self, buffer: &mut [u8]) {
+        self.0.random_source.fill_bytes(buffer)
+    }
+
+    fn sleep(&self, duration: Duration) -> Self::Delay {
+        self.sleep_until(self.0.clock.now() + duration)
+    }
+
+    fn sleep_until(&self, when: Self::Instant) -> Self::Delay {
+        self.0.clock.sleep_until(self.deadline(when))
+    }
+
+    fn spawn_task(
+        &self,
+        task_name: Cow<str>,
+        task: impl future::Future<Output = ()> + Send + 'static,
+    ) {
+        self.0.executor.spawn(&task_name, task.boxed())
+    }
+
+    fn client_name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.0.client_name)
+    }
+
+    fn client_version(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.0.client_version)
+    }
+
+    fn supports_connection_type(&self, connection_type: ConnectionType) -> bool {
+        !matches!(
+            connection_type,
+            ConnectionType::WebRtcIpv4 | ConnectionType::WebRtcIpv6
+        ) && self.0.connector.supports(connection_type)
+    }
+
+    fn connect_stream(&self, address: Address) -> Self::StreamConnectFuture {
+        self.0
+            .connector
+            .connect(address)
+            .map(|connection| connection.map(with_buffers::WithBuffers::new))
+            .boxed()
+    }
+
+    fn connect_multistream(&self, _address: MultiStreamAddress) -> Self::MultiStreamConnectFuture {
+        future::ready(Err(ConnectError {
+            message: "WebRTC connections aren't supported.".into(),
+        }))
+        .boxed()
+    }
+
+    fn open_out_substream(&self, connection: &mut Self::MultiStream) {
+        match *connection {}
+    }
+
+    fn next_substream<'a>(
+        &self,
+        connection: &'a mut Self::MultiStream,
+    ) -> Self::NextSubstreamFuture<'a> {
+        match *connection {}
+    }
+
+    fn read_write_access<'a>(
+        &self,
+        stream: Pin<&'a mut Self::Stream>,
+    ) -> Result<Self::ReadWriteAccess<'a>, Self::StreamErrorRef<'a>> {
+        stream.read_write_access(self.0.clock.now())
+    }
+
+    fn wait_read_write_again<'a>(
+        &self,
+        stream: Pin<&'a mut Self::Stream>,
+    ) -> Self::StreamUpdateFuture<'a> {
+        let platform = self.clone();
+        stream
+            .wait_read_write_again(move |when| platform.sleep_until(when))
+            .boxed()
+    }
+}
+
+struct AsyncStdExecutor;
+
+impl Executor for AsyncStdExecutor {
+    fn spawn(&self, _task_name: &str, task: BoxFuture<'static, ()>) {
+        async_std::task::spawn(task);
+    }
+}
+
+struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_from_unix_epoch(&self) -> Duration {
+        UNIX_EPOCH.elapsed().unwrap()
+    }
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep_until(&self, when: Instant) -> BoxFuture<'static, ()> {
+        async_std::task::sleep(when.saturating_duration_since(Instant::now())).boxed()
+    }
+}
+
+struct OsRandomSource;
+
+impl RandomSource for OsRandomSource {
+    fn fill_bytes(&self, buffer: &mut [u8]) {
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), buffer);
+    }
+}
+
+// Opens TCP connections, optionally negotiating WebSocket on top of them.
+pub(crate) struct TcpConnector {
+    tcp: bool,
+    websocket: bool,
+}
+
+impl TcpConnector {
+    /// Returns a connector that accepts plain TCP connections only if `tcp` is true, and WebSocket
+    /// ones only if `websocket` is true.
+    pub(crate) fn new(tcp: bool, websocket: bool) -> Self {
+        TcpConnector { tcp, websocket }
+    }
+}
+
+impl Connector for TcpConnector {
+    fn supports(&self, connection_type: ConnectionType) -> bool {
+        match connection_type {
+            ConnectionType::TcpIpv4 | ConnectionType::TcpIpv6 | ConnectionType::TcpDns => self.tcp,
+            ConnectionType::WebSocketIpv4 { .. }
+            | ConnectionType::WebSocketIpv6 { .. }
+            | ConnectionType::WebSocketDns { secure: false, .. } => self.websocket,
+            _ => false,
+        }
+    }
+
+    fn connect(&self, address: Address) -> BoxFuture<'static, Result<Connection, ConnectError>> {
+        // `(host, port)` to open the TCP connection to, and the `Host` header to send if the
+        // connection is a WebSocket one.
+        let (host, port, websocket_host) = match address {
+            Address::TcpDns { hostname, port } => (hostname.to_owned(), port, None),
+            Address::TcpIp { ip, port } => (socket_addr(ip, port).ip().to_string(), port, None),
+            Address::WebSocketDns {
+                hostname,
+                port,
+                secure: false,
+            } => (
+                hostname.to_owned(),
+                port,
+                Some(format!("{}:{}", hostname, port)),
+            ),
+            Address::WebSocketIp { ip, port } => {
+                let socket_addr = socket_addr(ip, port);
+                (
+                    socket_addr.ip().to_string(),
+                    port,
+                    Some(socket_addr.to_string()),
+                )
+            }
+            Address::WebSocketDns { secure: true, .. } => {
+                return future::ready(Err(ConnectError {
+                    message: "Secure WebSocket connections aren't supported.".into(),
+                }))
+                .boxed()
+            }
+        };
+
+        async move {
+            let tcp_socket = async_std::net::TcpStream::connect((&host[..], port))
+                .await
+                .map_err(|err| ConnectError {
+                    message: format!("Failed to reach peer: {}", err),
+                })?;
+            let _ = tcp_socket.set_nodelay(true);
+
+            let connection: Connection = match websocket_host {
+                Some(websocket_host) => Box::pin(
+                    websocket::websocket_client_handshake(websocket::Config {
+                        tcp_socket,
+                        host: &websocket_host,
+                        url: "/",
+                    })
+                    .await
+                    .map_err(|err| ConnectError {
+                        message: format!("Failed to negotiate WebSocket: {}", err),
+                    })?,
+                ),
+                None => Box::pin(tcp_socket),
+            };
+            Ok(connection)
+        }
+        .boxed()
+    }
+}
+
+fn socket_addr(ip: IpAddr, port: u16) -> SocketAddr {
+    match ip {
+        IpAddr::V4(ip) => SocketAddr::from((ip, port)),
+        IpAddr::V6(ip) => SocketAddr::from((ip, port)),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, RngCore as _, SeedableRng as _};
+    use std::{
+        sync::Mutex,
+        task::{Poll, Waker},
+    };
+
+    // Platform whose clock only moves when told to, whose randomness is seeded, and which can't
+    // connect to any peer, so that the tests neither depend on the network nor on timing.
+    pub(crate) fn deterministic_platform() -> (Platform, ManualClock) {
+        let clock = ManualClock::new();
+        let platform = Platform::builder("smoldot-flutter-tests".into(), "0.0.0".into())
+            .clock(clock.clone())
+            .random_source(SeededRandomSource(Mutex::new(StdRng::seed_from_u64(0))))
+            .connector(OfflineConnector)
+            .build();
+        (platform, clock)
+    }
+
+    #[derive(Clone)]
+    pub(crate) struct ManualClock(Arc<Mutex<ManualClockState>>);
+
+    struct ManualClockState {
+        origin: Instant,
+        elapsed: Duration,
+        sleepers: Vec<Waker>,
+    }
+
+    impl ManualClock {
+        fn new() -> Self {
+            ManualClock(Arc::new(Mutex::new(ManualClockState {
+                origin: Instant::now(),
+                elapsed: Duration::ZERO,
+                sleepers: Vec::new(),
+            })))
+        }
+
+        pub(crate) fn advance(&self, duration: Duration) {
+            let mut state = self.0.lock().unwrap();
+            state.elapsed += duration;
+            for sleeper in state.sleepers.drain(..) {
+                sleeper.wake();
+            }
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now_from_unix_epoch(&self) -> Duration {
+            // 2023-01-01T00:00:00Z
+            Duration::from_secs(1_672_531_200) + self.0.lock().unwrap().elapsed
+        }
+
+        fn now(&self) -> Instant {
+            let state = self.0.lock().unwrap();
+            state.origin + state.elapsed
+        }
+
+        fn sleep_until(&self, when: Instant) -> BoxFuture<'static, ()> {
+            let clock = self.clone();
+            future::poll_fn(move |cx| {
+                let mut state = clock.0.lock().unwrap();
+                if state.origin + state.elapsed >= when {
+                    return Poll::Ready(());
+                }
+                state.sleepers.push(cx.waker().clone());
+                Poll::Pending
+            })
+            .boxed()
+        }
+    }
+
+    struct SeededRandomSource(Mutex<StdRng>);
+
+    impl RandomSource for SeededRandomSource {
+        fn fill_bytes(&self, buffer: &mut [u8]) {
+            self.0.lock().unwrap().fill_bytes(buffer);
+        }
+    }
+
+    struct OfflineConnector;
+
+    impl Connector for OfflineConnector {
+        fn supports(&self, _: ConnectionType) -> bool {
+            true
+        }
+
+        fn connect(&self, _: Address) -> BoxFuture<'static, Result<Connection, ConnectError>> {
+            future::ready(Err(ConnectError {
+                message: "The test platform can't connect to peers.".into(),
+            }))
+            .boxed()
+        }
+    }
+
+    #[test]
+    fn fires_timers_when_clock_advances() {
+        let (platform, clock) = deterministic_platform();
+        let mut delay = platform.sleep(Duration::from_secs(10));
+
+        let waker = futures_util::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        assert!(delay.poll_unpin(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(5));
+        assert!(delay.poll_unpin(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(5));
+        assert!(delay.poll_unpin(&mut cx).is_ready());
+        assert!(platform
+            .sleep(Duration::ZERO)
+            .poll_unpin(&mut cx)
+            .is_ready());
+    }
+
+    #[test]
+    fn aligns_timers_while_throttled() {
+        let (platform, clock) = deterministic_platform();
+        let waker = futures_util::task::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+
+        platform.set_throttled(true);
+        let mut delay = platform.sleep(Duration::from_secs(1));
+        clock.advance(Duration::from_secs(4));
+        assert!(delay.poll_unpin(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(1));
+        assert!(delay.poll_unpin(&mut cx).is_ready());
+
+        platform.set_throttled(false);
+        let mut delay = platform.sleep(Duration::from_secs(1));
+        clock.advance(Duration::from_secs(1));
+        assert!(delay.poll_unpin(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn generates_same_randomness_every_time() {
+        let mut first = [0; 32];
+        let mut second = [0; 32];
+        deterministic_platform().0.fill_random_bytes(&mut first);
+        deterministic_platform().0.fill_random_bytes(&mut second);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn refuses_webrtc_connections() {
+        let platform = Platform::builder("smoldot-flutter-tests".into(), "0.0.0".into()).build();
+        assert!(platform.supports_connection_type(ConnectionType::TcpDns));
+        assert!(!platform.supports_connection_type(ConnectionType::WebRtcIpv4));
+        assert!(
+            !platform.supports_connection_type(ConnectionType::WebSocketDns {
+                secure: true,
+                remote_is_localhost: false,
+            })
+        );
+    }
+
+    #[test]
+    fn restricts_connections_to_allowed_transports() {
+        let websocket = ConnectionType::WebSocketIpv4 {
+            remote_is_localhost: false,
+        };
+        let platform = Platform::builder("smoldot-flutter-tests".into(), "0.0.0".into())
+            .connector(TcpConnector::new(false, true))
+            .build();
+        assert!(!platform.supports_connection_type(ConnectionType::TcpIpv4));
+        assert!(platform.supports_connection_type(websocket));
+
+        let platform = Platform::builder("smoldot-flutter-tests".into(), "0.0.0".into())
+            .connector(TcpConnector::new(true, false))
+            .build();
+        assert!(platform.supports_connection_type(ConnectionType::TcpIpv4));
+        assert!(!platform.supports_connection_type(websocket));
+    }
+}
@@ -0,0 +1,387 @@
+use crate::error::SmoldotFlutterError;
+use crate::json_rpc::{self, Batch, Router, Subscription, SubscriptionSink};
+use async_std::{
+    channel::{self, Receiver, Sender},
+    net::{Ipv4Addr, TcpListener, TcpStream},
+};
+use futures_util::{future, StreamExt};
+use log::debug;
+use serde_json::Value;
+use soketto::handshake::{self, server::Response};
+use std::{future::Future, io, sync::Arc};
+
+// Error code defined by the JSON-RPC 2.0 specification for messages that aren't valid JSON.
+const PARSE_ERROR: i64 = -32700;
+
+// Returns the chain a new connection is bound to, given the path of its URL.
+type Open = dyn Fn(&str) -> Option<Endpoint> + Send + Sync;
+
+type SendRequest = dyn Fn(&Arc<Router>, String) -> Result<(), SmoldotFlutterError> + Send + Sync;
+
+/// The chain a connection to the server is bound to.
+pub(crate) struct Endpoint {
+    /// Returns the router of the chain as it currently runs, which changes whenever the chain is
+    /// restarted, or `None` once the chain is stopped.
+    pub(crate) router: Box<dyn Fn() -> Option<Arc<Router>> + Send + Sync>,
+    /// Sends a request to the chain, provided that it still routes its responses through the
+    /// given router.
+    pub(crate) send_request: Box<SendRequest>,
+}
+
+/// JSON-RPC server serving the chains of a light client over WebSocket, on the loopback
+/// interface only.
+///
+/// A connection to `ws://127.0.0.1:<port>/<chain_name>` is bound to the chain with the given name.
+/// Every connection only receives the responses to its own requests and the notifications of its
+/// own subscriptions. Connections opened by web pages, which carry an `Origin` header, are rejected
+/// unless their origin is explicitly allowed. The server stops once dropped, closing its
+/// connections.
+pub(crate) struct JsonRpcServer {
+    port: u16,
+    // Never sent to, but closes the channel once dropped, which stops the server.
+    _stop: Sender<()>,
+}
+
+impl JsonRpcServer {
+    /// Starts listening on the given port, or on a port picked by the OS if 0, accepting the
+    /// connections of web pages from `allowed_origins` only.
+    ///
+    /// `open` returns the chain a new connection is bound to, given the path of its URL without
+    /// the leading `/`, or `None` if there is no such chain.
+    pub(crate) fn start(
+        port: u16,
+        allowed_origins: Vec<String>,
+        open: impl Fn(&str) -> Option<Endpoint> + Send + Sync + 'static,
+    ) -> io::Result<Self> {
+        let listener = async_std::task::block_on(TcpListener::bind((Ipv4Addr::LOCALHOST, port)))?;
+        let port = listener.local_addr()?.port();
+        let (stop, stopped) = channel::bounded(1);
+        async_std::task::spawn(until_stopped(
+            stopped.clone(),
+            accept(listener, Arc::new(open), allowed_origins.into(), stopped),
+        ));
+        Ok(JsonRpcServer { port, _stop: stop })
+    }
+
+    /// Returns the port the server listens on.
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+// Runs `future` until the server is stopped.
+async fn until_stopped(stopped: Receiver<()>, future: impl Future<Output = ()>) {
+    let stopped = async move {
+        let _ = stopped.recv().await;
+    };
+    futures_util::pin_mut!(future, stopped);
+    future::select(future, stopped).await;
+}
+
+async fn accept(
+    listener: TcpListener,
+    open: Arc<Open>,
+    allowed_origins: Arc<[String]>,
+    stopped: Receiver<()>,
+) {
+    let mut incoming = listener.incoming();
+    let mut next_connection_id = 0;
+    while let Some(stream) = incoming.next().await {
+        match stream {
+            Ok(stream) => {
+                let connection = Connection {
+                    id: next_connection_id,
+                    open: open.clone(),
+                    allowed_origins: allowed_origins.clone(),
+                };
+                next_connection_id += 1;
+                async_std::task::spawn(until_stopped(stopped.clone(), connect(stream, connection)));
+            }
+            Err(err) => debug!("Failed to accept JSON-RPC server connection: {}", err),
+        }
+    }
+}
+
+// What a new connection needs to know about the server.
+struct Connection {
+    // Tells apart the subscriptions of the connection from those of the others.
+    id: u64,
+    open: Arc<Open>,
+    allowed_origins: Arc<[String]>,
+}
+
+async fn connect(stream: TcpStream, connection: Connection) {
+    if let Err(err) = serve(stream, &connection).await {
+        debug!("JSON-RPC server connection failed: {}", err);
+    }
+}
+
+// Performs the WebSocket handshake, then answers the requests of the connection until either side
+// closes it, or until the chain it is bound to is stopped.
+async fn serve(stream: TcpStream, connection: &Connection) -> Result<(), handshake::Error> {
+    let mut server = handshake::Server::new(stream);
+    let (key, path, origin) = {
+        let request = server.receive_request().await?;
+        let origin = request
+            .headers()
+            .origin
+            .map(|origin| String::from_utf8_lossy(origin).into_owned());
+        (request.key(), request.path().to_owned(), origin)
+    };
+    // Browsers send the origin of the page opening a WebSocket, which no other client is expected
+    // to do, so that any web page could otherwise drive the light client.
+    if let Some(origin) = origin {
+        if !connection.allowed_origins.contains(&origin) {
+            debug!(
+                "JSON-RPC server connection from disallowed origin '{:?}'.",
+                origin
+            );
+            return server
+                .send_response(&Response::Reject { status_code: 403 })
+                .await;
+        }
+    }
+    let chain_name = path.trim_start_matches('/');
+    let Some(endpoint) = (connection.open)(chain_name) else {
+        debug!(
+            "JSON-RPC server connection to unknown chain '{:?}'.",
+            chain_name
+        );
+        return server
+            .send_response(&Response::Reject { status_code: 404 })
+            .await;
+    };
+    server
+        .send_response(&Response::Accept {
+            key,
+            protocol: None,
+        })
+        .await?;
+    debug!(
+        "New JSON-RPC server connection to chain '{:?}'.",
+        chain_name
+    );
+
+    // Responses and notifications are queued up for the connection, so that a slow client
+    // doesn't hold back the chain.
+    let (mut sender, mut receiver) = server.into_builder().finish();
+    let (messages, queued) = channel::unbounded::<String>();
+    let read = async {
+        loop {
+            let mut data = Vec::new();
+            match receiver.receive_data(&mut data).await {
+                Ok(soketto::Data::Text(_)) => match String::from_utf8(data) {
+                    Ok(message) => {
+                        if !handle(&endpoint, connection.id, &message, &messages) {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                },
+                Ok(soketto::Data::Binary(_)) => {}
+                Err(_) => break,
+            }
+        }
+    };
+    let write = async {
+        while let Ok(message) = queued.recv().await {
+            if sender.send_text_owned(message).await.is_err() || sender.flush().await.is_err() {
+                break;
+            }
+        }
+        let _ = sender.close().await;
+    };
+    futures_util::pin_mut!(read, write);
+    future::select(read, write).await;
+
+    // Cancels the subscriptions of the connection, which the chain might otherwise keep alive for
+    // as long as it runs.
+    messages.close();
+    if let Some(router) = (endpoint.router)() {
+        for request in router.unsubscribe_connection(connection.id) {
+            let _ = (endpoint.send_request)(&router, request);
+        }
+    }
+    debug!(
+        "JSON-RPC server connection to chain '{:?}' closed.",
+        chain_name
+    );
+    Ok(())
+}
+
+// Sends the request or batch of requests received from a connection to the chain as it currently
+// runs, and queues up its reply once every response has been received. Returns `false` if the
+// chain has been stopped, in which case the connection must be closed.
+fn handle(
+    endpoint: &Endpoint,
+    connection_id: u64,
+    message: &str,
+    messages: &Sender<String>,
+) -> bool {
+    let Some(router) = (endpoint.router)() else {
+        return false;
+    };
+    let (requests, is_batch) = match serde_json::from_str(message) {
+        Ok(Value::Array(requests)) if !requests.is_empty() => (requests, true),
+        Ok(request) => (vec![request], false),
+        Err(_) => {
+            let response = json_rpc::error_response(Value::Null, PARSE_ERROR, "Parse error");
+            let _ = messages.try_send(response.to_string());
+            return true;
+        }
+    };
+
+    let mut batch = Batch::new();
+    // Subscriptions that the requests cancel, along with the position of their response.
+    let mut cancelled = Vec::new();
+    for request in requests {
+        let subscription = subscription(&request, connection_id, messages);
+        let subscription_id = cancelled_subscription(&request);
+        let position = batch.send(&router, request, subscription, |request| {
+            (endpoint.send_request)(&router, request)
+        });
+        if let (Some(position), Some(subscription_id)) = (position, subscription_id) {
+            cancelled.push((position, subscription_id));
+        }
+    }
+    let messages = messages.clone();
+    async_std::task::spawn(async move {
+        if let Some(mut reply) = batch.reply().await {
+            for (position, subscription_id) in cancelled {
+                if reply[position].get("error").is_none() {
+                    router.forget(connection_id, &subscription_id);
+                }
+            }
+            let reply = if is_batch {
+                Value::Array(reply)
+            } else {
+                reply.remove(0)
+            };
+            let _ = messages.try_send(reply.to_string());
+        }
+    });
+    true
+}
+
+// Returns the subscription to register if the request starts one.
+fn subscription(
+    request: &Value,
+    connection_id: u64,
+    messages: &Sender<String>,
+) -> Option<Subscription> {
+    let method = request.get("method")?.as_str()?;
+    Some(Subscription {
+        sink: SubscriptionSink::Connection(connection_id, messages.clone()),
+        unsubscribe_method: unsubscribe_method(method)?,
+    })
+}
+
+// Returns the method cancelling the subscriptions started by `method`, if it starts any, going by
+// the naming conventions of the JSON-RPC API. A request that turns out not to start one, for
+// example because it failed, isn't registered as a subscription.
+fn unsubscribe_method(method: &str) -> Option<String> {
+    if is_unsubscribe_method(method) {
+        None
+    } else if method.contains("subscribe") {
+        Some(method.replacen("subscribe", "unsubscribe", 1))
+    } else if method.contains("submitAndWatch") {
+        Some(method.replacen("submitAndWatch", "unwatch", 1))
+    } else if let Some(prefix) = method.strip_suffix("_follow") {
+        Some(format!("{}_unfollow", prefix))
+    } else if let Some(prefix) = method.strip_prefix("chainHead_unstable_") {
+        // Operations started by the earlier versions of the chainHead API, whose notifications
+        // carry a subscription id of their own.
+        match prefix {
+            "body" => Some("chainHead_unstable_stopBody".to_owned()),
+            "call" => Some("chainHead_unstable_stopCall".to_owned()),
+            "storage" => Some("chainHead_unstable_stopStorage".to_owned()),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+fn is_unsubscribe_method(method: &str) -> bool {
+    method.contains("unsubscribe") || method.contains("unwatch") || method.ends_with("_unfollow")
+}
+
+// Returns the subscription that the request cancels, if any, which is its first parameter.
+fn cancelled_subscription(request: &Value) -> Option<String> {
+    if !is_unsubscribe_method(request.get("method")?.as_str()?) {
+        return None;
+    }
+    let subscription_id = match request.get("params")? {
+        Value::Array(params) => params.first()?,
+        Value::Object(params) => params.values().next()?,
+        _ => return None,
+    };
+    match subscription_id {
+        Value::String(id) => Some(id.clone()),
+        Value::Number(id) => Some(id.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_unsubscribe_methods() {
+        for (method, expected) in [
+            ("chain_subscribeNewHeads", Some("chain_unsubscribeNewHeads")),
+            ("subscribe_newHead", Some("unsubscribe_newHead")),
+            ("state_subscribeStorage", Some("state_unsubscribeStorage")),
+            (
+                "author_submitAndWatchExtrinsic",
+                Some("author_unwatchExtrinsic"),
+            ),
+            (
+                "transaction_unstable_submitAndWatch",
+                Some("transaction_unstable_unwatch"),
+            ),
+            (
+                "chainHead_unstable_follow",
+                Some("chainHead_unstable_unfollow"),
+            ),
+            (
+                "chainHead_unstable_body",
+                Some("chainHead_unstable_stopBody"),
+            ),
+            ("chain_unsubscribeNewHeads", None),
+            ("chainHead_unstable_unfollow", None),
+            ("system_chain", None),
+        ] {
+            assert_eq!(
+                unsubscribe_method(method).as_deref(),
+                expected,
+                "{}",
+                method
+            );
+        }
+    }
+
+    #[test]
+    fn finds_cancelled_subscriptions() {
+        let request = |method: &str, params: Value| serde_json::json!({"jsonrpc":"2.0","id":1,"method":method,"params":params});
+        assert_eq!(
+            cancelled_subscription(&request(
+                "chain_unsubscribeNewHeads",
+                serde_json::json!(["a"])
+            )),
+            Some("a".to_owned())
+        );
+        assert_eq!(
+            cancelled_subscription(&request(
+                "chainHead_unstable_unfollow",
+                serde_json::json!({"followSubscription": 7})
+            )),
+            Some("7".to_owned())
+        );
+        assert_eq!(
+            cancelled_subscription(&request("chain_subscribeNewHeads", serde_json::json!([]))),
+            None
+        );
+    }
+}
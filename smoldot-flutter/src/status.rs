@@ -0,0 +1,212 @@
+use crate::{error::SmoldotFlutterError, util::hex};
+use serde_json::Value;
+use smoldot::header;
+
+/// Sync status of a chain, as forwarded by `watch_chain_status`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainStatus {
+    /// Number of the best block, or `None` until it is known.
+    pub best_block_number: Option<u64>,
+    /// Hash of the best block, in `0x`-prefixed hexadecimal, or `None` until it is known.
+    pub best_block_hash: Option<String>,
+    /// Number of the latest finalized block, or `None` until it is known.
+    pub finalized_block_number: Option<u64>,
+    /// Hash of the latest finalized block, in `0x`-prefixed hexadecimal, or `None` until it is
+    /// known.
+    pub finalized_block_hash: Option<String>,
+    /// Number of peers the chain is syncing from.
+    pub peers: u32,
+    /// Whether the chain is still catching up with its head, in which case its best and finalized
+    /// blocks aren't recent.
+    pub is_syncing: bool,
+    /// Progress of the warp sync of the chain.
+    pub warp_sync_phase: WarpSyncPhase,
+}
+
+/// Event of the stream returned by `watch_chain_status`.
+pub enum ChainStatusEvent {
+    /// The current sync status of the chain.
+    Status(ChainStatus),
+    /// Why the status of the chain couldn't be followed, after which the stream ends.
+    Err(SmoldotFlutterError),
+}
+
+/// Progress of the warp sync of a chain, which catches up with the finalized block of its peers
+/// before following the chain block by block, as far as its JSON-RPC service tells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarpSyncPhase {
+    /// The chain has no peer to warp sync from yet.
+    WaitingForPeers,
+    /// The chain is warp syncing from its peers.
+    InProgress,
+    /// The chain has caught up with its head once. It is considered syncing again if it falls
+    /// behind afterwards, but doesn't warp sync again.
+    Finished,
+}
+
+/// Builds the status of a chain from the headers of its new best and finalized blocks, and from its
+/// health.
+pub(crate) struct StatusTracker {
+    status: ChainStatus,
+}
+
+impl StatusTracker {
+    pub(crate) fn new() -> Self {
+        StatusTracker {
+            status: ChainStatus {
+                best_block_number: None,
+                best_block_hash: None,
+                finalized_block_number: None,
+                finalized_block_hash: None,
+                peers: 0,
+                is_syncing: true,
+                warp_sync_phase: WarpSyncPhase::WaitingForPeers,
+            },
+        }
+    }
+
+    pub(crate) fn status(&self) -> &ChainStatus {
+        &self.status
+    }
+
+    /// Takes into account a header notified by `chain_subscribeNewHeads`.
+    pub(crate) fn best_head(&mut self, header: &Value) {
+        if let Some((number, hash)) = block_of(header) {
+            self.status.best_block_number = Some(number);
+            self.status.best_block_hash = Some(hash);
+        }
+    }
+
+    /// Takes into account a header notified by `chain_subscribeFinalizedHeads`.
+    pub(crate) fn finalized_head(&mut self, header: &Value) {
+        if let Some((number, hash)) = block_of(header) {
+            self.status.finalized_block_number = Some(number);
+            self.status.finalized_block_hash = Some(hash);
+        }
+    }
+
+    /// Takes into account the result of `system_health`.
+    pub(crate) fn health(&mut self, health: &str) {
+        let Ok(health) = serde_json::from_str::<Value>(health) else {
+            return;
+        };
+        if let Some(peers) = health["peers"].as_u64() {
+            self.status.peers = u32::try_from(peers).unwrap_or(u32::MAX);
+        }
+        if let Some(is_syncing) = health["isSyncing"].as_bool() {
+            self.status.is_syncing = is_syncing;
+        }
+        self.status.warp_sync_phase = match self.status.warp_sync_phase {
+            WarpSyncPhase::Finished => WarpSyncPhase::Finished,
+            _ if !self.status.is_syncing => WarpSyncPhase::Finished,
+            _ if self.status.peers == 0 => WarpSyncPhase::WaitingForPeers,
+            _ => WarpSyncPhase::InProgress,
+        };
+    }
+}
+
+// Returns the number and hash of the block of a header in the JSON format of the legacy JSON-RPC
+// API, which doesn't include the hash. The hash is computed from the SCALE encoding of the header,
+// which is rebuilt from its fields.
+fn block_of(header: &Value) -> Option<(u64, String)> {
+    let number = u64::from_str_radix(header["number"].as_str()?.strip_prefix("0x")?, 16).ok()?;
+    let logs = header["digest"]["logs"].as_array()?;
+
+    let mut encoded = Vec::new();
+    encoded.extend(unhex_hash(&header["parentHash"])?);
+    encode_compact(number, &mut encoded);
+    encoded.extend(unhex_hash(&header["stateRoot"])?);
+    encoded.extend(unhex_hash(&header["extrinsicsRoot"])?);
+    encode_compact(u64::try_from(logs.len()).ok()?, &mut encoded);
+    for log in logs {
+        encoded.extend(unhex(log.as_str()?)?);
+    }
+    let hash = header::hash_from_scale_encoded_header(&encoded);
+    Some((number, format!("0x{}", hex(&hash))))
+}
+
+fn unhex_hash(hash: &Value) -> Option<[u8; 32]> {
+    unhex(hash.as_str()?)?.try_into().ok()
+}
+
+fn unhex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.strip_prefix("0x")?;
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// Appends the SCALE compact encoding of the value.
+fn encode_compact(value: u64, to: &mut Vec<u8>) {
+    match value {
+        0..=0x3f => to.push((value as u8) << 2),
+        0x40..=0x3fff => to.extend_from_slice(&((value as u16) << 2 | 0b01).to_le_bytes()),
+        0x4000..=0x3fff_ffff => to.extend_from_slice(&((value as u32) << 2 | 0b10).to_le_bytes()),
+        _ => {
+            let len = 8 - value.leading_zeros() as usize / 8;
+            to.push(((len - 4) as u8) << 2 | 0b11);
+            to.extend_from_slice(&value.to_le_bytes()[..len]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smoldot::json_rpc::methods;
+
+    // Returns a header in the format of the legacy JSON-RPC API, alongside its hash.
+    fn header(number: u64) -> (Value, String) {
+        let header = header::Header {
+            parent_hash: [1; 32],
+            number,
+            state_root: [2; 32],
+            extrinsics_root: [3; 32],
+            digest: header::DigestRef::empty().into(),
+        };
+        let encoded = header.scale_encoding_vec(4);
+        let json = methods::Header::from_scale_encoded_header(&encoded, 4).unwrap();
+        let hash = format!("0x{}", hex(&header.hash(4)));
+        (serde_json::to_value(json).unwrap(), hash)
+    }
+
+    #[test]
+    fn hashes_headers() {
+        for number in [0, 63, 64, 16_383, 16_384, 1 << 30, u64::MAX] {
+            let (header, hash) = header(number);
+            assert_eq!(block_of(&header), Some((number, hash)));
+        }
+        assert_eq!(block_of(&serde_json::json!({ "number": "0x1" })), None);
+    }
+
+    #[test]
+    fn tracks_chain_status() {
+        let mut tracker = StatusTracker::new();
+        assert_eq!(
+            tracker.status().warp_sync_phase,
+            WarpSyncPhase::WaitingForPeers
+        );
+
+        tracker.health(r#"{"isSyncing":true,"peers":3,"shouldHavePeers":true}"#);
+        assert_eq!(tracker.status().peers, 3);
+        assert_eq!(tracker.status().warp_sync_phase, WarpSyncPhase::InProgress);
+        let (best, best_hash) = header(12);
+        let (finalized, finalized_hash) = header(10);
+        tracker.best_head(&best);
+        tracker.finalized_head(&finalized);
+        assert_eq!(tracker.status().best_block_number, Some(12));
+        assert_eq!(tracker.status().best_block_hash, Some(best_hash));
+        assert_eq!(tracker.status().finalized_block_number, Some(10));
+        assert_eq!(tracker.status().finalized_block_hash, Some(finalized_hash));
+
+        tracker.health(r#"{"isSyncing":false,"peers":3,"shouldHavePeers":true}"#);
+        assert_eq!(tracker.status().warp_sync_phase, WarpSyncPhase::Finished);
+        tracker.health(r#"{"isSyncing":true,"peers":0,"shouldHavePeers":true}"#);
+        assert!(tracker.status().is_syncing);
+        assert_eq!(tracker.status().warp_sync_phase, WarpSyncPhase::Finished);
+    }
+}
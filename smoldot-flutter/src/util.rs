@@ -0,0 +1,10 @@
+use std::fmt::Write as _;
+
+/// Returns the bytes in lowercase hexadecimal, without prefix.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02x}", byte);
+    }
+    hex
+}